@@ -4,8 +4,9 @@ use luno::{Currency, LunoClient};
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let client = LunoClient::new("LUNO_API_KEY", "LUNO_API_SECRET");
 
-	Ok(println!(
+	println!(
 		"{:?}",
 		client.create_account(Currency::XBT, "My Account").await?
-	))
+	);
+	Ok(())
 }
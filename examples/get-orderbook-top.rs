@@ -4,8 +4,6 @@ use luno::{LunoClient, TradingPair};
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let client = LunoClient::new("LUNO_API_KEY", "LUNO_API_SECRET");
 
-	Ok(println!(
-		"{:?}",
-		client.get_orderbook_top(TradingPair::XBTZAR).await?
-	))
+	println!("{:?}", client.get_orderbook_top(TradingPair::XBTZAR).await?);
+	Ok(())
 }
@@ -6,7 +6,7 @@ use luno::{LimitOrderType, LunoClient, StopDirection, TradingPair};
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let client = LunoClient::new("LUNO_API_KEY", "LUNO_API_SECRET");
 
-	Ok(println!(
+	println!(
 		"{:?}",
 		client
 			.limit_order(
@@ -20,5 +20,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 			.post_only()
 			.post()
 			.await?
-	))
+	);
+	Ok(())
 }
@@ -0,0 +1,16 @@
+use luno::{LunoClient, TradingPair};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+	let client = LunoClient::new("LUNO_API_KEY", "LUNO_API_SECRET");
+
+	println!(
+		"{:?}",
+		client
+			.candles(TradingPair::XBTZAR, 1390168800000, 3600)
+			.until(1390255200000)
+			.list()
+			.await?
+	);
+	Ok(())
+}
@@ -4,8 +4,9 @@ use luno::LunoClient;
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let client = LunoClient::new("LUNO_API_KEY", "LUNO_API_SECRET");
 
-	Ok(println!(
+	println!(
 		"{:?}",
 		client.list_pending_transactions("ACCOUNT_ID").await?
-	))
+	);
+	Ok(())
 }
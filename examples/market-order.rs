@@ -6,11 +6,12 @@ use luno::{LunoClient, MarketOrderType, TradingPair};
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let client = LunoClient::new("LUNO_API_KEY", "LUNO_API_SECRET");
 
-	Ok(println!(
+	println!(
 		"{:?}",
 		client
 			.market_order(TradingPair::XBTZAR, MarketOrderType::BUY, dec!(0.0))
 			.post()
 			.await?
-	))
+	);
+	Ok(())
 }
@@ -1,9 +1,15 @@
+use std::time::Duration;
+
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use crate::de::deserialize_id;
 use crate::market::Currency;
 use crate::{client, error::LunoError};
 
+/// The interval between requests made by `wait_for_move()` to check on a move.
+const MOVE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Account {
 	pub id: String,
@@ -61,3 +67,108 @@ impl<'a> ListBalancesBuilder<'a> {
 			.balance)
 	}
 }
+
+string_enum! {
+	/// Represents the status of a move of funds between your accounts.
+	pub enum MoveStatus {
+		CREATED,
+		MOVING,
+		SUCCESSFUL,
+		FAILED,
+	}
+}
+
+impl MoveStatus {
+	/// Returns whether the move has finished, whether or not it was successful.
+	pub fn is_final(&self) -> bool {
+		matches!(self, MoveStatus::SUCCESSFUL | MoveStatus::FAILED)
+	}
+}
+
+/// Represents a move of funds between two of your accounts of the same currency.
+#[derive(Debug, Deserialize)]
+pub struct Move {
+	pub id: String,
+	/// The client move ID given when the move was requested, if any.
+	#[serde(default)]
+	pub client_move_id: Option<String>,
+	#[serde(deserialize_with = "deserialize_id")]
+	pub debit_account_id: String,
+	#[serde(deserialize_with = "deserialize_id")]
+	pub credit_account_id: String,
+	pub amount: Decimal,
+	/// The UNIX timestamp at which the move was requested.
+	pub created_at: u64,
+	/// The UNIX timestamp at which the status of the move last changed.
+	pub updated_at: u64,
+	pub status: MoveStatus,
+}
+
+/// Contains the ID and initial status of a requested move.
+#[derive(Debug, Deserialize)]
+pub struct MoveFundsResponse {
+	pub id: String,
+	pub status: MoveStatus,
+}
+
+/// Contains a list of moves.
+#[derive(Debug, Deserialize)]
+pub struct ListMovesResponse {
+	#[serde(default)]
+	pub moves: Vec<Move>,
+}
+
+/// A builder for the `list_moves()` method.
+pub struct ListMovesBuilder<'a> {
+	pub(crate) luno_client: &'a client::LunoClient,
+	pub(crate) url: reqwest::Url,
+	pub(crate) before: Option<u64>,
+	pub(crate) limit: Option<u64>,
+}
+
+impl<'a> ListMovesBuilder<'a> {
+	/// Only returns moves requested before the given UNIX timestamp.
+	pub fn before(&mut self, timestamp: u64) -> &mut ListMovesBuilder<'a> {
+		self.before = Some(timestamp);
+		self
+	}
+
+	pub fn limit(&mut self, count: u64) -> &mut ListMovesBuilder<'a> {
+		self.limit = Some(count);
+		self
+	}
+
+	pub async fn list(&self) -> Result<Vec<Move>, LunoError> {
+		let mut url = self.url.clone();
+		if let Some(timestamp) = self.before {
+			url.query_pairs_mut()
+				.append_pair("before", &timestamp.to_string());
+		}
+		if let Some(limit) = self.limit {
+			url.query_pairs_mut()
+				.append_pair("limit", &limit.to_string());
+		}
+		Ok(self.luno_client.get::<ListMovesResponse>(url).await?.moves)
+	}
+}
+
+/// Polls a move until it has finished, returning `LunoError::Timeout` if it has not finished
+/// within `timeout`.
+pub(crate) async fn wait_for_move(
+	luno_client: &client::LunoClient,
+	id: &str,
+	timeout: Duration,
+) -> Result<Move, LunoError> {
+	let poll = async {
+		loop {
+			let move_ = luno_client.get_move(id).await?;
+			if move_.status.is_final() {
+				return Ok(move_);
+			}
+			tokio::time::sleep(MOVE_POLL_INTERVAL).await;
+		}
+	};
+	tokio::time::timeout(timeout, poll)
+		.await
+		.unwrap_or(Err(LunoError::Timeout))
+}
@@ -1,4 +1,10 @@
+use std::collections::HashMap;
+
+use reqwest::Url;
 use serde::Deserialize;
+use strum_macros::{Display, EnumString};
+
+use crate::{error::LunoError, LunoClient};
 
 #[derive(Debug, Deserialize)]
 pub struct Beneficiary {
@@ -16,3 +22,52 @@ pub struct Beneficiary {
 pub struct ListBeneficiariesResponse {
 	pub beneficiaries: Vec<Beneficiary>,
 }
+
+/// Represents the type of a beneficiary's bank account.
+#[derive(EnumString, Display, Debug, Clone, PartialEq)]
+pub enum BankAccountType {
+	#[strum(serialize = "Current/Cheque")]
+	Current,
+	Savings,
+	Transmission,
+}
+
+/// A builder for the `create_beneficiary()` method.
+pub struct CreateBeneficiaryBuilder<'a> {
+	pub(crate) luno_client: &'a LunoClient,
+	pub(crate) url: Url,
+	pub(crate) params: HashMap<&'a str, String>,
+}
+
+impl<'a> CreateBeneficiaryBuilder<'a> {
+	/// Sets the branch code of the bank account, which some banks require.
+	pub fn with_branch_code(&mut self, branch_code: &str) -> &mut CreateBeneficiaryBuilder<'a> {
+		self.params
+			.insert("branch_code", branch_code.trim().to_owned());
+		self
+	}
+
+	/// Validates the beneficiary and creates it.
+	pub async fn post(&mut self) -> Result<Beneficiary, LunoError> {
+		self.validate()?;
+		let url = self.url.clone();
+		self.luno_client.post(url, &self.params).await
+	}
+
+	fn validate(&self) -> Result<(), LunoError> {
+		for field in &["bank_name", "bank_recipient", "bank_account_number"] {
+			if !matches!(self.params.get(field), Some(value) if !value.is_empty()) {
+				return Err(LunoError::InvalidRequest(format!("{} is required", field)));
+			}
+		}
+		if let Some(code) = self.params.get("branch_code") {
+			if !code.chars().all(|c| c.is_ascii_digit()) {
+				return Err(LunoError::InvalidRequest(format!(
+					"branch_code must only contain digits, got {:?}",
+					code
+				)));
+			}
+		}
+		Ok(())
+	}
+}
@@ -1,38 +1,81 @@
 use std::collections::HashMap;
 use std::string::ToString;
+use std::time::Duration;
 
-use reqwest::Client;
+use futures::{stream, StreamExt, TryStreamExt};
+use reqwest::{
+	header::{HeaderMap, RETRY_AFTER},
+	Client, RequestBuilder, StatusCode, Url,
+};
 use rust_decimal::Decimal;
-use serde::de::DeserializeOwned;
+use serde::{
+	de::{DeserializeOwned, IgnoredAny},
+	Serialize,
+};
 
+use crate::accounts::{self, ListMovesBuilder, Move, MoveFundsResponse};
+use crate::rate_limit::RateLimiter;
 use crate::{
-	error::LunoError, Account, Beneficiary, CancelOrderResponse, CreateQuoteBuilder, Credentials,
-	Currency, FeeInfo, LimitOrderType, ListBalancesBuilder, ListBeneficiariesResponse,
-	ListOrdersBuilder, ListOwnTradesBuilder, ListPendingTransactionsResponse, ListTickersResponse,
-	ListTradesResponse, ListTransactionsResponse, MarketOrderType, Order, Orderbook,
-	PostLimitOrderBuilder, PostMarketOrderBuilder, Quote, Ticker, Trade, TradingPair,
-	UpdateAccountNameResponse, UrlMaker,
+	error::LunoError, orders::OrderState, Account, BankAccountType, Beneficiary,
+	CancelOrderResponse, CancelledOrder, CreateBeneficiaryBuilder, CreateQuoteBuilder,
+	CreateWithdrawalBuilder, Credentials, Currency, FeeInfo, FundingAddress, LightningInvoice,
+	LightningReceiveBuilder, LightningSendBuilder, LimitOrderType, ListBalancesBuilder,
+	ListBeneficiariesResponse, ListCandlesBuilder, ListMarketsResponse, ListOrdersBuilder,
+	ListOwnTradesBuilder, ListPendingTransactionsResponse, ListTickersResponse, ListTradesResponse,
+	ListTransactionsResponse, ListWithdrawalsBuilder, MarketInfo, MarketOrderType, Order,
+	OrderHandle, Orderbook, OrderbookStream, PostLimitOrderBuilder, PostMarketOrderBuilder, Quote,
+	RetryPolicy, SendBuilder, SendFee, Ticker, Trade, TradingPair, TransactionsBuilder,
+	UpdateAccountNameResponse, UrlMaker, UserStream, ValidateAddressBuilder, Withdrawal,
+	WithdrawalType,
 };
 
+/// The number of orders cancelled at a time by `cancel_all_orders()`.
+const CANCEL_CONCURRENCY: usize = 10;
+
 const API_BASE: &str = "https://api.luno.com/api/1/";
+const STREAM_BASE: &str = "wss://ws.luno.com/api/1/";
+
+/// Luno allows roughly 300 requests per minute for each API key.
+const DEFAULT_RATE_LIMIT: (u32, Duration) = (300, Duration::from_secs(60));
 
 /// The top level client for interacting with the Luno API.
 pub struct LunoClient {
 	pub(crate) credentials: Credentials,
 	pub(crate) http: Client,
 	pub(crate) url_maker: UrlMaker,
+	pub(crate) retry_policy: Option<RetryPolicy>,
+	pub(crate) rate_limiter: Option<RateLimiter>,
 }
 
 impl LunoClient {
 	pub fn new<T: AsRef<str>>(key: T, secret: T) -> LunoClient {
 		let credentials = Credentials::new(key, secret);
 		let http = Client::new();
-		let url_maker = UrlMaker::new(API_BASE);
+		let url_maker = UrlMaker::new(API_BASE, STREAM_BASE);
 
 		LunoClient {
 			credentials,
 			url_maker,
 			http,
+			retry_policy: None,
+			rate_limiter: Some(RateLimiter::new(DEFAULT_RATE_LIMIT.0, DEFAULT_RATE_LIMIT.1)),
+		}
+	}
+
+	/// Returns a `LunoClientBuilder` that allows you to configure the base URL
+	/// and the underlying HTTP client before creating a `LunoClient`.
+	pub fn builder<T: AsRef<str>>(key: T, secret: T) -> LunoClientBuilder {
+		LunoClientBuilder {
+			credentials: Credentials::new(key, secret),
+			base_url: API_BASE.to_owned(),
+			stream_url: STREAM_BASE.to_owned(),
+			http: None,
+			timeout: None,
+			connect_timeout: None,
+			user_agent: None,
+			default_headers: None,
+			retry_policy: None,
+			rate_limit: Some(DEFAULT_RATE_LIMIT),
 		}
 	}
 
@@ -40,51 +83,135 @@ impl LunoClient {
 	where
 		T: DeserializeOwned,
 	{
-		Ok(self
-			.http
-			.get(url)
-			.basic_auth(
-				self.credentials.key.to_owned(),
-				Some(self.credentials.secret.to_owned()),
-			)
-			.send()
-			.await?
-			.json::<T>()
-			.await?)
+		self.send_request(self.http.get(url), true).await
 	}
 
 	pub(crate) async fn put<T>(&self, url: reqwest::Url) -> Result<T, LunoError>
 	where
 		T: DeserializeOwned,
 	{
-		Ok(self
-			.http
-			.put(url)
-			.basic_auth(
-				self.credentials.key.to_owned(),
-				Some(self.credentials.secret.to_owned()),
-			)
-			.send()
-			.await?
-			.json::<T>()
-			.await?)
+		self.send_request(self.http.put(url), true).await
 	}
 
 	pub(crate) async fn delete<T>(&self, url: reqwest::Url) -> Result<T, LunoError>
 	where
 		T: DeserializeOwned,
 	{
-		Ok(self
-			.http
-			.delete(url)
-			.basic_auth(
-				self.credentials.key.to_owned(),
-				Some(self.credentials.secret.to_owned()),
-			)
-			.send()
-			.await?
-			.json::<T>()
-			.await?)
+		self.send_request(self.http.delete(url), true).await
+	}
+
+	pub(crate) async fn post<T, P>(&self, url: reqwest::Url, params: &P) -> Result<T, LunoError>
+	where
+		T: DeserializeOwned,
+		P: Serialize + ?Sized,
+	{
+		self.send_request(self.http.post(url).form(params), false)
+			.await
+	}
+
+	/// Posts a request that can safely be repeated, such as an order with a client order ID,
+	/// so that it is retried according to the retry policy.
+	pub(crate) async fn post_idempotent<T, P>(
+		&self,
+		url: reqwest::Url,
+		params: &P,
+	) -> Result<T, LunoError>
+	where
+		T: DeserializeOwned,
+		P: Serialize + ?Sized,
+	{
+		self.send_request(self.http.post(url).form(params), true)
+			.await
+	}
+
+	/// Posts a request that can safely be repeated if `params` contains the unique ID named by
+	/// `id_key`, retrying it according to the retry policy only then.
+	pub(crate) async fn post_maybe_idempotent<T>(
+		&self,
+		url: reqwest::Url,
+		params: &HashMap<&str, String>,
+		id_key: &str,
+	) -> Result<T, LunoError>
+	where
+		T: DeserializeOwned,
+	{
+		let idempotent = params.contains_key(id_key);
+		self.send_request(self.http.post(url).form(params), idempotent)
+			.await
+	}
+
+	/// Authenticates and sends a request, retrying it according to the retry policy
+	/// if it is `idempotent` or if it was rejected because of rate limiting.
+	async fn send_request<T>(
+		&self,
+		request: RequestBuilder,
+		idempotent: bool,
+	) -> Result<T, LunoError>
+	where
+		T: DeserializeOwned,
+	{
+		let request = request.basic_auth(
+			self.credentials.key.to_owned(),
+			Some(self.credentials.secret.to_owned()),
+		);
+
+		let mut attempt = 1;
+		loop {
+			let attempt_request = request
+				.try_clone()
+				.expect("requests without streaming bodies can be cloned");
+			let result = self.execute(attempt_request).await;
+			let delay = match (&self.retry_policy, &result) {
+				(Some(policy), Err(e)) if attempt < policy.max_attempts => match e {
+					// Rate limited requests are rejected before being processed,
+					// so they are safe to retry even if they are not idempotent.
+					LunoError::RateLimited { retry_after } => {
+						let backoff = policy.backoff(attempt);
+						retry_after.map_or(backoff, |retry_after| retry_after.max(backoff))
+					}
+					e if idempotent && policy.is_retryable(e) => policy.backoff(attempt),
+					_ => return result,
+				},
+				_ => return result,
+			};
+			tokio::time::sleep(delay).await;
+			attempt += 1;
+		}
+	}
+
+	/// Sends a request once the rate limiter allows it, deserializing the response body
+	/// into `T` or into a `LunoError` if Luno rejected the request.
+	async fn execute<T>(&self, request: RequestBuilder) -> Result<T, LunoError>
+	where
+		T: DeserializeOwned,
+	{
+		if let Some(rate_limiter) = &self.rate_limiter {
+			rate_limiter.acquire().await;
+		}
+		let response = request.send().await?;
+		let status = response.status();
+
+		if status == StatusCode::TOO_MANY_REQUESTS {
+			let retry_after = response
+				.headers()
+				.get(RETRY_AFTER)
+				.and_then(|value| value.to_str().ok())
+				.and_then(|value| value.trim().parse().ok())
+				.map(Duration::from_secs);
+			if let (Some(rate_limiter), Some(retry_after)) = (&self.rate_limiter, retry_after) {
+				rate_limiter.pause_for(retry_after);
+			}
+			return Err(LunoError::RateLimited { retry_after });
+		}
+
+		let body = response.text().await?;
+		if !status.is_success() {
+			return Err(LunoError::from_response(status, body));
+		}
+		serde_json::from_str(&body).map_err(|e| match LunoError::from_response(status, body) {
+			LunoError::UnexpectedResponse { .. } => LunoError::DeserializationError(e),
+			api_error => api_error,
+		})
 	}
 
 	/// Returns the latest ticker indicators.
@@ -117,6 +244,26 @@ impl LunoClient {
 		self.get(url).await
 	}
 
+	/// Connects to the Streaming API and returns a live order book for the market,
+	/// which is kept up to date with `OrderbookStream::next_update()`.
+	pub async fn stream_orderbook(&self, pair: TradingPair) -> Result<OrderbookStream, LunoError> {
+		let url = self.url_maker.stream(pair);
+		OrderbookStream::connect(url, self.credentials.clone()).await
+	}
+
+	/// Connects to the user stream of the Streaming API, which sends events when your
+	/// orders change status or are filled and when your balances change.
+	pub async fn user_stream(&self) -> Result<UserStream, LunoError> {
+		let url = self.url_maker.user_stream();
+		UserStream::connect(url, self.credentials.clone()).await
+	}
+
+	/// Returns the trading status, volume and price limits and precision of all markets.
+	pub async fn list_markets(&self) -> Result<Vec<MarketInfo>, LunoError> {
+		let url = self.url_maker.markets();
+		Ok(self.get::<ListMarketsResponse>(url).await?.markets)
+	}
+
 	/// Returns a list of the most recent trades that happened in the last 24h.
 	/// At most 100 results are returned per call.
 	pub async fn list_trades(&self, pair: TradingPair) -> Result<Vec<Trade>, LunoError> {
@@ -124,6 +271,22 @@ impl LunoClient {
 		Ok(self.get::<ListTradesResponse>(url).await?.trades)
 	}
 
+	/// Get candlestick market data for a market, starting at the `since` UNIX timestamp
+	/// in milliseconds, with each candle covering `duration` seconds.
+	///
+	/// The duration must be one of `market::CANDLE_DURATIONS`. Note that `candles()` returns a
+	/// `ListCandlesBuilder` that fetches all candles up to the present, or up to the
+	/// timestamp given to `until()`, using as many calls as needed.
+	pub fn candles(&self, pair: TradingPair, since: u64, duration: u64) -> ListCandlesBuilder<'_> {
+		ListCandlesBuilder {
+			luno_client: self,
+			url: self.url_maker.candles(pair),
+			since,
+			duration,
+			until: None,
+		}
+	}
+
 	/// This request creates an account for the specified currency.
 	/// Please note that the balances for the Account will be displayed based on the `asset` value,
 	/// which is the currency the account is based on.
@@ -139,18 +302,7 @@ impl LunoClient {
 		params.insert("currency", currency.to_string());
 		params.insert("name", name.to_string());
 
-		Ok(self
-			.http
-			.post(url)
-			.basic_auth(
-				self.credentials.key.to_owned(),
-				Some(self.credentials.secret.to_owned()),
-			)
-			.form(&params)
-			.send()
-			.await?
-			.json::<Account>()
-			.await?)
+		self.post(url, &params).await
 	}
 
 	/// Update the name of an account with a given ID,
@@ -165,6 +317,72 @@ impl LunoClient {
 		self.put(url).await
 	}
 
+	/// Move funds between two of your accounts of the same currency.
+	///
+	/// A `client_move_id` makes the move safe to retry (see `RetryPolicy`).
+	/// The move is made asynchronously; use `get_move()` or `wait_for_move()` to follow it.
+	///
+	/// Permissions required: `Perm_W_Transfers`
+	pub async fn move_funds(
+		&self,
+		debit_account_id: &str,
+		credit_account_id: &str,
+		amount: Decimal,
+		client_move_id: Option<&str>,
+	) -> Result<MoveFundsResponse, LunoError> {
+		let url = self.url_maker.moves();
+		let mut params = HashMap::new();
+		params.insert("debit_account_id", debit_account_id.to_string());
+		params.insert("credit_account_id", credit_account_id.to_string());
+		params.insert("amount", amount.to_string());
+		if let Some(id) = client_move_id {
+			params.insert("client_move_id", id.to_string());
+		}
+		self.post_maybe_idempotent(url, &params, "client_move_id")
+			.await
+	}
+
+	/// Get a move by its ID.
+	///
+	/// Permissions required: `Perm_R_Transfers`
+	pub async fn get_move(&self, id: &str) -> Result<Move, LunoError> {
+		let mut url = self.url_maker.moves();
+		url.query_pairs_mut().append_pair("id", id);
+		self.get(url).await
+	}
+
+	/// Get a move by the client move ID it was requested with.
+	///
+	/// Permissions required: `Perm_R_Transfers`
+	pub async fn get_move_by_client_id(&self, client_move_id: &str) -> Result<Move, LunoError> {
+		let mut url = self.url_maker.moves();
+		url.query_pairs_mut()
+			.append_pair("client_move_id", client_move_id);
+		self.get(url).await
+	}
+
+	/// Poll a move until it has succeeded or failed, returning `LunoError::Timeout`
+	/// if it has not finished within `timeout`.
+	///
+	/// Permissions required: `Perm_R_Transfers`
+	pub async fn wait_for_move(&self, id: &str, timeout: Duration) -> Result<Move, LunoError> {
+		accounts::wait_for_move(self, id, timeout).await
+	}
+
+	/// Get a list of your most recent moves.
+	/// Note that `list_moves()` returns a `ListMovesBuilder`
+	/// that allows you to chain a limit and timestamp filter onto your request.
+	///
+	/// Permissions required: `Perm_R_Transfers`
+	pub fn list_moves(&self) -> ListMovesBuilder<'_> {
+		ListMovesBuilder {
+			luno_client: self,
+			url: self.url_maker.list_moves(),
+			before: None,
+			limit: None,
+		}
+	}
+
 	/// Get a list of all accounts and their respective balances for the requesting user.
 	///
 	/// Note that `balances()` returns a `ListBalancesBuilder`
@@ -173,7 +391,7 @@ impl LunoClient {
 	/// method on `ListBalancesBuilder`.
 	///
 	/// Permissions required: `Perm_R_Balance`.
-	pub fn balances(&self) -> ListBalancesBuilder {
+	pub fn balances(&self) -> ListBalancesBuilder<'_> {
 		ListBalancesBuilder {
 			assets: None,
 			url: self.url_maker.balance(),
@@ -201,6 +419,21 @@ impl LunoClient {
 		self.get(url).await
 	}
 
+	/// Walk the transaction entries of an account across as many pages as needed.
+	/// Note that `transactions()` returns a `TransactionsBuilder` that allows you to
+	/// choose the order and range of rows before calling `stream()`.
+	///
+	/// Permissions required: `Perm_R_Transactions`.
+	pub fn transactions(&self, account_id: &str) -> TransactionsBuilder<'_> {
+		TransactionsBuilder {
+			luno_client: self,
+			account_id: account_id.to_owned(),
+			after_row: None,
+			before_row: None,
+			newest_first: false,
+		}
+	}
+
 	/// Return a list of all transactions that have not completed for the account.
 	///
 	/// Pending transactions are not numbered, and may be reordered, deleted or updated at any time.
@@ -225,11 +458,43 @@ impl LunoClient {
 			.beneficiaries)
 	}
 
+	/// Create a new bank beneficiary. The beneficiary is validated before it is posted,
+	/// and a branch code can be set on the returned `CreateBeneficiaryBuilder`.
+	///
+	/// Permissions required: Perm_W_Beneficiaries
+	pub fn create_beneficiary(
+		&self,
+		account_type: BankAccountType,
+		bank_name: &str,
+		account_number: &str,
+		recipient: &str,
+	) -> CreateBeneficiaryBuilder<'_> {
+		let mut params = HashMap::new();
+		params.insert("account_type", account_type.to_string());
+		params.insert("bank_name", bank_name.trim().to_string());
+		params.insert("bank_account_number", account_number.trim().to_string());
+		params.insert("bank_recipient", recipient.trim().to_string());
+		CreateBeneficiaryBuilder {
+			luno_client: self,
+			url: self.url_maker.beneficiaries(),
+			params,
+		}
+	}
+
+	/// Delete a bank beneficiary.
+	///
+	/// Permissions required: Perm_W_Beneficiaries
+	pub async fn delete_beneficiary(&self, id: &str) -> Result<(), LunoError> {
+		let url = self.url_maker.beneficiary(id);
+		self.delete::<IgnoredAny>(url).await?;
+		Ok(())
+	}
+
 	/// Get a list of the most recently placed orders.
 	/// Note that `list_orders()` returns a `ListOrdersBuilder`
 	/// that allows you chain pair and state filters onto your
 	/// request.
-	pub fn orders(&self) -> ListOrdersBuilder {
+	pub fn orders(&self) -> ListOrdersBuilder<'_> {
 		ListOrdersBuilder {
 			luno_client: self,
 			url: self.url_maker.list_orders(),
@@ -253,16 +518,17 @@ impl LunoClient {
 		order_type: LimitOrderType,
 		volume: Decimal,
 		price: Decimal,
-	) -> PostLimitOrderBuilder {
-		let mut params = HashMap::new();
-		params.insert("pair", pair.to_string());
-		params.insert("type", order_type.to_string());
-		params.insert("volume", volume.to_string());
-		params.insert("price", price.to_string());
+	) -> PostLimitOrderBuilder<'_> {
 		PostLimitOrderBuilder {
 			luno_client: self,
 			url: self.url_maker.post_order(),
-			params,
+			pair,
+			order_type,
+			volume,
+			price,
+			stop_price: None,
+			market: None,
+			params: HashMap::new(),
 		}
 	}
 
@@ -281,7 +547,7 @@ impl LunoClient {
 		pair: TradingPair,
 		order_type: MarketOrderType,
 		volume: Decimal,
-	) -> PostMarketOrderBuilder {
+	) -> PostMarketOrderBuilder<'_> {
 		let mut params = HashMap::new();
 		params.insert("pair", pair.to_string());
 		params.insert("type", order_type.to_string());
@@ -302,18 +568,48 @@ impl LunoClient {
 		let mut params = HashMap::new();
 		params.insert("order_id", order_id.to_string());
 
-		Ok(self
-			.http
-			.post(url)
-			.basic_auth(
-				self.credentials.key.to_owned(),
-				Some(self.credentials.secret.to_owned()),
-			)
-			.form(&params)
-			.send()
-			.await?
-			.json::<CancelOrderResponse>()
-			.await?)
+		self.post(url, &params).await
+	}
+
+	/// Cancel all of your open orders, or only those for `pair` if it is given. This includes
+	/// stop orders that are still waiting to trigger (`OrderState::AWAITING`).
+	///
+	/// All pages of open orders are listed first, then the orders are cancelled concurrently
+	/// within the client's rate limit. An error is only returned if the open orders could not
+	/// be listed; the outcome of cancelling each order
+	/// is reported in the returned list.
+	pub async fn cancel_all_orders(
+		&self,
+		pair: Option<TradingPair>,
+	) -> Result<Vec<CancelledOrder>, LunoError> {
+		// List every page before cancelling, so that cancellations don't shift the pages.
+		let mut orders: Vec<Order> = Vec::new();
+		for state in [OrderState::AWAITING, OrderState::PENDING].iter() {
+			let mut list = self.orders();
+			list.filter_state(state.clone());
+			if let Some(pair) = &pair {
+				list.filter_pair(pair.clone());
+			}
+			let listed: Vec<Order> = list.stream().try_collect().await?;
+			orders.extend(listed);
+		}
+
+		Ok(stream::iter(orders)
+			.map(|order| async move {
+				let result = self.cancel_order(&order.order_id).await;
+				CancelledOrder {
+					order_id: order.order_id,
+					result,
+				}
+			})
+			.buffer_unordered(CANCEL_CONCURRENCY)
+			.collect()
+			.await)
+	}
+
+	/// Track an order as it is filled, e.g. to wait for it to complete.
+	pub fn track_order(&self, order_id: &str) -> OrderHandle<'_> {
+		OrderHandle::new(self, order_id)
 	}
 
 	/// Get an order by its ID.
@@ -322,6 +618,16 @@ impl LunoClient {
 		self.get(url).await
 	}
 
+	/// Get an order by the client order ID it was placed with.
+	///
+	/// Permissions required: `Perm_R_Orders`
+	pub async fn get_order_by_client_id(&self, client_order_id: &str) -> Result<Order, LunoError> {
+		let mut url = self.url_maker.order_v3();
+		url.query_pairs_mut()
+			.append_pair("client_order_id", client_order_id);
+		self.get(url).await
+	}
+
 	/// Returns a list of your recent trades for a given pair, sorted by oldest first. If `before` is specified, then the trades are returned sorted by most recent first.
 	///
 	/// `type` in the response indicates the type of order that you placed in order to participate in the trade. Possible types: `BID`, `ASK`.
@@ -329,7 +635,7 @@ impl LunoClient {
 	/// If `is_buy` in the response is true, then the order which completed the trade (market taker) was a bid order.
 	///
 	/// Results of this query may lag behind the latest data.
-	pub fn list_own_trades(&self, pair: TradingPair) -> ListOwnTradesBuilder {
+	pub fn list_own_trades(&self, pair: TradingPair) -> ListOwnTradesBuilder<'_> {
 		ListOwnTradesBuilder {
 			luno_client: self,
 			url: self.url_maker.list_trades(pair),
@@ -349,6 +655,92 @@ impl LunoClient {
 		self.get(url).await
 	}
 
+	/// Create a Lightning invoice to receive XBT into your default XBT account.
+	///
+	/// Permissions required: `Perm_W_Send`
+	pub fn lightning_receive(&self, amount: Decimal) -> LightningReceiveBuilder<'_> {
+		let mut params = HashMap::new();
+		params.insert("currency", Currency::XBT.to_string());
+		params.insert("amount", amount.to_string());
+		LightningReceiveBuilder {
+			luno_client: self,
+			url: self.url_maker.lightning_receive(),
+			params,
+		}
+	}
+
+	/// Get the status of a Lightning invoice created with `lightning_receive()`.
+	///
+	/// Permissions required: `Perm_R_Transactions`
+	pub async fn get_lightning_invoice(&self, id: &str) -> Result<LightningInvoice, LunoError> {
+		let url = self.url_maker.lightning_invoice(id);
+		self.get(url).await
+	}
+
+	/// Pay a Lightning invoice from your default XBT account.
+	///
+	/// Warning! Lightning payments cannot be reversed once they are sent.
+	///
+	/// Permissions required: `Perm_W_Send`
+	pub fn lightning_send(&self, payment_request: &str) -> LightningSendBuilder<'_> {
+		let mut params = HashMap::new();
+		params.insert("currency", Currency::XBT.to_string());
+		params.insert("payment_request", payment_request.to_string());
+		LightningSendBuilder {
+			luno_client: self,
+			url: self.url_maker.lightning_send(),
+			params,
+		}
+	}
+
+	/// Get a list of your withdrawals, most recent first.
+	/// Note that `list_withdrawals()` returns a `ListWithdrawalsBuilder`
+	/// that allows you to chain a limit and starting point onto your request.
+	///
+	/// Permissions required: `Perm_R_Withdrawals`
+	pub fn list_withdrawals(&self) -> ListWithdrawalsBuilder<'_> {
+		ListWithdrawalsBuilder {
+			luno_client: self,
+			url: self.url_maker.withdrawals(),
+			limit: None,
+			before_id: None,
+		}
+	}
+
+	/// Get the details of a withdrawal by its ID.
+	///
+	/// Permissions required: `Perm_R_Withdrawals`
+	pub async fn get_withdrawal(&self, id: &str) -> Result<Withdrawal, LunoError> {
+		let url = self.url_maker.withdrawal(id);
+		self.get(url).await
+	}
+
+	/// Request a withdrawal of fiat to one of your beneficiaries.
+	///
+	/// Permissions required: `Perm_W_Withdrawals`
+	pub fn create_withdrawal(
+		&self,
+		withdrawal_type: WithdrawalType,
+		amount: Decimal,
+	) -> CreateWithdrawalBuilder<'_> {
+		let mut params = HashMap::new();
+		params.insert("type", withdrawal_type.to_string());
+		params.insert("amount", amount.to_string());
+		CreateWithdrawalBuilder {
+			luno_client: self,
+			url: self.url_maker.withdrawals(),
+			params,
+		}
+	}
+
+	/// Cancel a withdrawal that is still pending.
+	///
+	/// Permissions required: `Perm_W_Withdrawals`
+	pub async fn cancel_withdrawal(&self, id: &str) -> Result<Withdrawal, LunoError> {
+		let url = self.url_maker.withdrawal(id);
+		self.delete(url).await
+	}
+
 	/// Creates a new quote to buy or sell a particular amount of a base currency for a counter currency.
 	///
 	/// Users can specify either the exact amount to pay or the exact amount to receive.
@@ -365,7 +757,7 @@ impl LunoClient {
 		order_type: MarketOrderType,
 		base_amount: Decimal,
 		pair: TradingPair,
-	) -> CreateQuoteBuilder {
+	) -> CreateQuoteBuilder<'_> {
 		let mut params = HashMap::new();
 		params.insert("type", order_type.to_string());
 		params.insert("base_amount", base_amount.to_string());
@@ -377,6 +769,101 @@ impl LunoClient {
 		}
 	}
 
+	/// Returns the default receive address associated with your account and the amount received
+	/// via the address, or the given `address` if it belongs to your account.
+	///
+	/// Permissions required: `Perm_R_Addresses`
+	pub async fn get_funding_address(
+		&self,
+		asset: Currency,
+		address: Option<&str>,
+	) -> Result<FundingAddress, LunoError> {
+		let mut url = self.url_maker.funding_address();
+		url.query_pairs_mut().append_pair("asset", asset.as_str());
+		if let Some(address) = address {
+			url.query_pairs_mut().append_pair("address", address);
+		}
+		self.get(url).await
+	}
+
+	/// Allocates a new receive address to your account, optionally with a name and for
+	/// an account other than your default account for the asset.
+	///
+	/// Permissions required: `Perm_W_Addresses`
+	pub async fn create_funding_address(
+		&self,
+		asset: Currency,
+		name: Option<&str>,
+		account_id: Option<&str>,
+	) -> Result<FundingAddress, LunoError> {
+		let url = self.url_maker.funding_address();
+		let mut params = HashMap::new();
+		params.insert("asset", asset.to_string());
+		if let Some(name) = name {
+			params.insert("name", name.to_string());
+		}
+		if let Some(account_id) = account_id {
+			params.insert("account_id", account_id.to_string());
+		}
+
+		self.post(url, &params).await
+	}
+
+	/// Send cryptocurrency from your account to a cryptocurrency address.
+	///
+	/// Warning! Cryptocurrency transactions cannot be reversed once they are sent.
+	///
+	/// Fails with `ErrorCode::ErrInsufficientBalance` if the account cannot cover the amount
+	/// and fee, or `ErrorCode::ErrInvalidAddress` if the address is not valid for the currency.
+	///
+	/// Permissions required: `Perm_W_Send`
+	pub fn send(&self, amount: Decimal, currency: Currency, address: &str) -> SendBuilder<'_> {
+		let mut params = HashMap::new();
+		params.insert("amount", amount.to_string());
+		params.insert("currency", currency.to_string());
+		params.insert("address", address.to_string());
+		SendBuilder {
+			luno_client: self,
+			url: self.url_maker.send(),
+			params,
+			validation: None,
+		}
+	}
+
+	/// Validate a cryptocurrency address before sending funds to it. Travel rule details about
+	/// who the address belongs to can be set on the returned `ValidateAddressBuilder`.
+	///
+	/// Pass the builder to `SendBuilder::with_address_validation()` to run this check before sending.
+	///
+	/// Permissions required: `Perm_W_Send`
+	pub fn validate_address(
+		&self,
+		address: &str,
+		currency: Currency,
+	) -> ValidateAddressBuilder<'_> {
+		let mut params = HashMap::new();
+		params.insert("address", address.to_string());
+		params.insert("currency", currency.to_string());
+		ValidateAddressBuilder {
+			luno_client: self,
+			url: self.url_maker.validate_address(),
+			params,
+		}
+	}
+
+	/// Calculate the fee charged for sending an amount of cryptocurrency to an address.
+	///
+	/// Permissions required: `Perm_W_Send`
+	pub async fn send_fee(
+		&self,
+		amount: Decimal,
+		currency: Currency,
+		address: &str,
+	) -> Result<SendFee, LunoError> {
+		let url = self.url_maker.send_fee(amount, &currency, address);
+		self.get(url).await
+	}
+
 	/// Get the latest status of a quote by its id.
 	///
 	/// Permissions required: `Perm_R_Orders`
@@ -405,3 +892,160 @@ impl LunoClient {
 		self.delete(url).await
 	}
 }
+
+/// A builder for the `LunoClient::builder()` method.
+pub struct LunoClientBuilder {
+	credentials: Credentials,
+	base_url: String,
+	stream_url: String,
+	http: Option<Client>,
+	timeout: Option<Duration>,
+	connect_timeout: Option<Duration>,
+	user_agent: Option<String>,
+	default_headers: Option<HeaderMap>,
+	retry_policy: Option<RetryPolicy>,
+	rate_limit: Option<(u32, Duration)>,
+}
+
+impl LunoClientBuilder {
+	/// Sets the root of the API that requests are made against, such as a mock server or a proxy.
+	///
+	/// The URL should include the API version path, e.g. `https://api.luno.com/api/1/`.
+	pub fn with_base_url(&mut self, url: &str) -> &mut LunoClientBuilder {
+		self.base_url = url.to_owned();
+		self
+	}
+
+	/// Sets the root of the Streaming API, such as a local websocket server.
+	///
+	/// The URL should include the API version path, e.g. `wss://ws.luno.com/api/1/`.
+	pub fn with_stream_url(&mut self, url: &str) -> &mut LunoClientBuilder {
+		self.stream_url = url.to_owned();
+		self
+	}
+
+	/// Uses a pre-built `reqwest::Client` for all requests.
+	///
+	/// The timeouts, user agent and default headers of this builder cannot be applied to a
+	/// pre-built client, so `build()` returns an error if any of them are also set.
+	pub fn with_http_client(&mut self, http: Client) -> &mut LunoClientBuilder {
+		self.http = Some(http);
+		self
+	}
+
+	/// Sets the timeout for each request, from connecting until the response body has been read.
+	pub fn with_timeout(&mut self, timeout: Duration) -> &mut LunoClientBuilder {
+		self.timeout = Some(timeout);
+		self
+	}
+
+	/// Sets the timeout for connecting to the API.
+	pub fn with_connect_timeout(&mut self, timeout: Duration) -> &mut LunoClientBuilder {
+		self.connect_timeout = Some(timeout);
+		self
+	}
+
+	/// Sets the `User-Agent` header sent with each request.
+	pub fn with_user_agent(&mut self, user_agent: &str) -> &mut LunoClientBuilder {
+		self.user_agent = Some(user_agent.to_owned());
+		self
+	}
+
+	/// Sets headers that are sent with each request.
+	pub fn with_default_headers(&mut self, headers: HeaderMap) -> &mut LunoClientBuilder {
+		self.default_headers = Some(headers);
+		self
+	}
+
+	/// Retries failed idempotent requests according to `policy`.
+	/// By default requests are not retried.
+	pub fn with_retry_policy(&mut self, policy: RetryPolicy) -> &mut LunoClientBuilder {
+		self.retry_policy = Some(policy);
+		self
+	}
+
+	/// Limits requests to `requests` every `per`, shared by all requests made through the client.
+	/// By default requests are limited to Luno's budget of 300 requests per minute.
+	///
+	/// Requests that would exceed the limit wait until they can be sent.
+	pub fn with_rate_limit(&mut self, requests: u32, per: Duration) -> &mut LunoClientBuilder {
+		self.rate_limit = Some((requests, per));
+		self
+	}
+
+	/// Sends requests without limiting their rate on the client side.
+	pub fn without_rate_limit(&mut self) -> &mut LunoClientBuilder {
+		self.rate_limit = None;
+		self
+	}
+
+	/// Creates the `LunoClient` with the specified configuration.
+	///
+	/// Fails with `LunoError::InvalidRequest` if the base or stream URL is not an HTTP or
+	/// WebSocket URL that paths can be appended to.
+	pub fn build(&self) -> Result<LunoClient, LunoError> {
+		let url_maker = UrlMaker::from_urls(
+			parse_base_url(&self.base_url)?,
+			parse_base_url(&self.stream_url)?,
+		);
+
+		let http = match &self.http {
+			Some(_)
+				if self.timeout.is_some()
+					|| self.connect_timeout.is_some()
+					|| self.user_agent.is_some()
+					|| self.default_headers.is_some() =>
+			{
+				return Err(LunoError::InvalidConfig(
+					"timeouts, user agent and default headers cannot be applied to a pre-built HTTP client"
+						.to_owned(),
+				));
+			}
+			Some(http) => http.clone(),
+			None => {
+				let mut builder = Client::builder();
+				if let Some(timeout) = self.timeout {
+					builder = builder.timeout(timeout);
+				}
+				if let Some(timeout) = self.connect_timeout {
+					builder = builder.connect_timeout(timeout);
+				}
+				if let Some(user_agent) = &self.user_agent {
+					builder = builder.user_agent(user_agent);
+				}
+				if let Some(headers) = &self.default_headers {
+					builder = builder.default_headers(headers.clone());
+				}
+				builder.build()?
+			}
+		};
+
+		Ok(LunoClient {
+			credentials: self.credentials.clone(),
+			url_maker,
+			http,
+			retry_policy: self.retry_policy.clone(),
+			rate_limiter: self
+				.rate_limit
+				.map(|(requests, per)| RateLimiter::new(requests, per)),
+		})
+	}
+}
+
+/// Parses the root of an API, ensuring that paths are appended to it rather than replacing its last segment.
+///
+/// The URL must be an HTTP or WebSocket URL that paths can be appended to.
+fn parse_base_url(url: &str) -> Result<Url, LunoError> {
+	let parsed = if url.ends_with('/') {
+		Url::parse(url)?
+	} else {
+		Url::parse(&format!("{}/", url))?
+	};
+	if parsed.cannot_be_a_base() || !matches!(parsed.scheme(), "http" | "https" | "ws" | "wss") {
+		return Err(LunoError::InvalidRequest(format!(
+			"{} is not an HTTP or WebSocket base URL",
+			url
+		)));
+	}
+	Ok(parsed)
+}
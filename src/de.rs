@@ -0,0 +1,32 @@
+use serde::{Deserialize, Deserializer};
+
+/// An ID, which some endpoints send as a number rather than a string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Id {
+	Number(u64),
+	Text(String),
+}
+
+impl From<Id> for String {
+	fn from(id: Id) -> String {
+		match id {
+			Id::Number(id) => id.to_string(),
+			Id::Text(id) => id,
+		}
+	}
+}
+
+/// Deserializes an ID sent as either a number or a string.
+pub(crate) fn deserialize_id<'de, D: Deserializer<'de>>(
+	deserializer: D,
+) -> Result<String, D::Error> {
+	Ok(Id::deserialize(deserializer)?.into())
+}
+
+/// Deserializes an optional ID sent as either a number or a string.
+pub(crate) fn deserialize_optional_id<'de, D: Deserializer<'de>>(
+	deserializer: D,
+) -> Result<Option<String>, D::Error> {
+	Ok(Option::<Id>::deserialize(deserializer)?.map(String::from))
+}
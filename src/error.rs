@@ -1,11 +1,128 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde::Deserialize;
 use thiserror::Error;
 
+string_enum! {
+	/// Represents the `error_code` returned by the Luno API when a request fails.
+	pub enum ErrorCode {
+		ErrAccountNotFound,
+		ErrAmountTooSmall,
+		ErrApiKeyRevoked,
+		ErrDuplicateClientOrderID,
+		ErrInsufficientBalance,
+		ErrInsufficientFunds,
+		ErrInsufficientPerms,
+		ErrInternal,
+		ErrInvalidAddress,
+		ErrInvalidArguments,
+		ErrInvalidMarketPair,
+		ErrInvalidPrice,
+		ErrInvalidVolume,
+		ErrMarketUnavailable,
+		ErrNotFound,
+		ErrOrderCanceled,
+		ErrOrderNotFound,
+		ErrPostOnlyMode,
+		ErrPriceTooHigh,
+		ErrPriceTooLow,
+		ErrTooManyRequests,
+		ErrUnauthorised,
+		ErrUnderMaintenance,
+		ErrVolumeTooHigh,
+		ErrVolumeTooLow,
+	}
+}
+
 /// LunoError is the wrapper error type for this crate to help differentiate it from
 /// more generic errors in your application.
 #[derive(Error, Debug)]
 pub enum LunoError {
 	#[error("Network error encountered")]
 	HttpError(reqwest::Error),
+	/// The Luno API rejected the request and described why.
+	#[error("Luno API error ({status}): {code}: {message}")]
+	ApiError {
+		/// The HTTP status of the response.
+		status: StatusCode,
+		/// The `error_code` returned by Luno.
+		code: ErrorCode,
+		/// The human readable `error` message returned by Luno.
+		message: String,
+		/// Any `error_action` details returned by Luno.
+		action: Option<HashMap<String, String>>,
+	},
+	/// Luno rejected the request because too many requests were made.
+	/// Requests made through the same client wait at least `retry_after` before being sent.
+	#[error("Rate limited by Luno")]
+	RateLimited { retry_after: Option<Duration> },
+	/// The request failed with a body that is not a Luno error, e.g. from a proxy.
+	#[error("Unexpected response ({status}): {body}")]
+	UnexpectedResponse { status: StatusCode, body: String },
+	/// The response body could not be deserialized into the expected type.
+	#[error("Failed to deserialize response")]
+	DeserializationError(serde_json::Error),
+	/// The request was rejected before being sent because it would fail, e.g. an order
+	/// volume below the minimum for the market.
+	#[error("Invalid request: {0}")]
+	InvalidRequest(String),
+	/// The connection to the Streaming API failed.
+	#[error("Streaming API error")]
+	WebSocketError(Box<tokio_tungstenite::tungstenite::Error>),
+	/// The Streaming API closed the connection before sending any data.
+	#[error("Streaming API connection closed")]
+	StreamClosed,
+	/// The base URL given to `LunoClientBuilder` could not be parsed.
+	#[error("Invalid URL: {0}")]
+	InvalidUrl(url::ParseError),
+	/// The options given to `LunoClientBuilder` cannot be used together.
+	#[error("Invalid client configuration: {0}")]
+	InvalidConfig(String),
+	/// Waiting timed out, e.g. for an order to complete.
+	#[error("Timed out")]
+	Timeout,
+	/// A full page of results was created at `timestamp`, in milliseconds, so the results
+	/// after it cannot be listed without skipping some.
+	#[error("A full page of results was created at {timestamp}")]
+	PageFull { timestamp: u64 },
+}
+
+impl LunoError {
+	/// Builds the error for a response whose body is not the expected type,
+	/// using the Luno error details in the body where present.
+	pub(crate) fn from_response(status: StatusCode, body: String) -> LunoError {
+		match serde_json::from_str::<ErrorResponse>(&body) {
+			Ok(response) => LunoError::ApiError {
+				status,
+				code: response.error_code,
+				message: response.error,
+				action: response.error_action,
+			},
+			Err(_) => LunoError::UnexpectedResponse { status, body },
+		}
+	}
+
+	/// Returns the Luno error code, if the error was returned by the Luno API.
+	pub fn error_code(&self) -> Option<&ErrorCode> {
+		match self {
+			LunoError::ApiError { code, .. } => Some(code),
+			_ => None,
+		}
+	}
+
+	/// Returns the HTTP status of the response that caused the error, if there was one.
+	pub fn status(&self) -> Option<StatusCode> {
+		match self {
+			LunoError::HttpError(e) => e.status(),
+			LunoError::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+			LunoError::ApiError { status, .. } | LunoError::UnexpectedResponse { status, .. } => {
+				Some(*status)
+			}
+			_ => None,
+		}
+	}
 }
 
 impl From<reqwest::Error> for LunoError {
@@ -13,3 +130,71 @@ impl From<reqwest::Error> for LunoError {
 		LunoError::HttpError(item)
 	}
 }
+
+impl From<serde_json::Error> for LunoError {
+	fn from(item: serde_json::Error) -> Self {
+		LunoError::DeserializationError(item)
+	}
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for LunoError {
+	fn from(item: tokio_tungstenite::tungstenite::Error) -> Self {
+		LunoError::WebSocketError(Box::new(item))
+	}
+}
+
+impl From<url::ParseError> for LunoError {
+	fn from(item: url::ParseError) -> Self {
+		LunoError::InvalidUrl(item)
+	}
+}
+
+/// The body returned by the Luno API when a request fails.
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+	error: String,
+	error_code: ErrorCode,
+	error_action: Option<HashMap<String, String>>,
+}
+
+#[cfg(test)]
+mod tests {
+	use reqwest::StatusCode;
+
+	use super::{ErrorCode, LunoError};
+
+	#[test]
+	fn test_api_error() {
+		let body = r#"{"error":"Insufficient balance","error_code":"ErrInsufficientBalance","error_action":{"hint":"deposit"}}"#;
+		let error = LunoError::from_response(StatusCode::BAD_REQUEST, body.into());
+		assert_eq!(error.error_code(), Some(&ErrorCode::ErrInsufficientBalance));
+		assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+		match error {
+			LunoError::ApiError {
+				message, action, ..
+			} => {
+				assert_eq!(message, "Insufficient balance");
+				assert_eq!(action.unwrap()["hint"], "deposit");
+			}
+			e => panic!("unexpected error: {:?}", e),
+		}
+	}
+
+	#[test]
+	fn test_unknown_error_code() {
+		let body = r#"{"error":"Something new","error_code":"ErrSomethingNew"}"#;
+		let error = LunoError::from_response(StatusCode::OK, body.into());
+		assert_eq!(
+			error.error_code(),
+			Some(&ErrorCode::Other("ErrSomethingNew".into()))
+		);
+	}
+
+	#[test]
+	fn test_unexpected_response() {
+		let body = "<html>Bad Gateway</html>";
+		let error = LunoError::from_response(StatusCode::BAD_GATEWAY, body.into());
+		assert!(matches!(error, LunoError::UnexpectedResponse { .. }));
+		assert_eq!(error.status(), Some(StatusCode::BAD_GATEWAY));
+	}
+}
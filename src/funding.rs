@@ -0,0 +1,27 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::Currency;
+
+/// Represents an address that receives cryptocurrency into your account.
+#[derive(Debug, Deserialize)]
+pub struct FundingAddress {
+	pub asset: Currency,
+	pub address: String,
+	/// The name given to the address, if any.
+	#[serde(default)]
+	pub name: Option<String>,
+	/// The ID of the account that funds sent to the address are received into.
+	#[serde(default)]
+	pub account_id: Option<String>,
+	/// The UNIX timestamp at which the address was assigned to your account.
+	#[serde(default)]
+	pub assigned_at: Option<u64>,
+	/// The total amount received by the address that has been confirmed.
+	pub total_received: Decimal,
+	/// The total amount sent to the address that has not been confirmed yet.
+	pub total_unconfirmed: Decimal,
+	/// A URI that can be encoded as a QR code for the address.
+	#[serde(default)]
+	pub qr_code_uri: Option<String>,
+}
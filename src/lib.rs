@@ -5,33 +5,65 @@
 //! Go to [https://www.luno.com/wallet/security/api_keys](https://www.luno.com/wallet/security/api_keys) to
 //! create a new API key with appropriate permissions.
 
+#[macro_use]
+mod macros;
+
 pub mod accounts;
 pub mod beneficiaries;
 pub mod client;
 pub mod error;
+pub mod funding;
+pub mod lightning;
 pub mod market;
 pub mod orders;
 pub mod quotes;
+pub mod retry;
+pub mod send;
+pub mod stream;
 pub mod trades;
 pub mod transactions;
+pub mod withdrawals;
 
 mod credentials;
+mod de;
+mod rate_limit;
 mod urls;
 
-pub use accounts::{Account, Balance, ListBalancesBuilder, UpdateAccountNameResponse};
-pub use beneficiaries::{Beneficiary, ListBeneficiariesResponse};
-pub use client::LunoClient;
+pub use accounts::{
+	Account, Balance, ListBalancesBuilder, ListMovesBuilder, ListMovesResponse, Move,
+	MoveFundsResponse, MoveStatus, UpdateAccountNameResponse,
+};
+pub use beneficiaries::{
+	BankAccountType, Beneficiary, CreateBeneficiaryBuilder, ListBeneficiariesResponse,
+};
+pub use client::{LunoClient, LunoClientBuilder};
 pub use credentials::Credentials;
+pub use funding::FundingAddress;
+pub use lightning::{
+	LightningInvoice, LightningPayment, LightningReceiveBuilder, LightningSendBuilder,
+	LightningStatus,
+};
 pub use market::{
-	Ask, Bid, Currency, ListTickersResponse, ListTradesResponse, Orderbook, Ticker, Trade,
-	TradingPair,
+	Ask, Bid, Candle, Currency, FillEstimate, ListCandlesBuilder, ListCandlesResponse,
+	ListMarketsResponse, ListTickersResponse, ListTradesResponse, MarketInfo, Orderbook, Ticker,
+	Trade, TradingPair, TradingStatus,
 };
 pub use orders::{
-	CancelOrderResponse, LimitOrderType, ListOrdersBuilder, ListOrdersResponse, MarketOrderType,
-	Order, PostLimitOrderBuilder, PostMarketOrderBuilder, PostOrderResponse, StopDirection,
+	CancelOrderResponse, CancelledOrder, LimitOrderType, ListOrdersBuilder, ListOrdersResponse,
+	MarketOrderType, Order, OrderHandle, OrderType, PostLimitOrderBuilder, PostMarketOrderBuilder,
+	PostOrderResponse, StopDirection, TimeInForce,
 };
 pub use quotes::{CreateQuoteBuilder, Quote};
+pub use retry::RetryPolicy;
+pub use send::{AddressValidation, SendBuilder, SendFee, SendResponse, ValidateAddressBuilder};
+pub use stream::{OrderbookStream, OrderbookUpdate, UserEvent, UserStream};
 pub use trades::{FeeInfo, ListOwnTradesBuilder, OwnTrade};
-pub use transactions::{ListPendingTransactionsResponse, ListTransactionsResponse, Transaction};
+pub use transactions::{
+	ListPendingTransactionsResponse, ListTransactionsResponse, Transaction, TransactionsBuilder,
+};
+pub use withdrawals::{
+	CreateWithdrawalBuilder, ListWithdrawalsBuilder, ListWithdrawalsResponse, Withdrawal,
+	WithdrawalStatus, WithdrawalType,
+};
 
 use urls::UrlMaker;
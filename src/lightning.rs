@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use reqwest::Url;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::de::deserialize_id;
+use crate::{error::LunoError, LunoClient};
+
+string_enum! {
+	/// Represents the status of a Lightning invoice or payment.
+	pub enum LightningStatus {
+		/// The invoice has not been paid, or the payment has not been settled yet.
+		PENDING,
+		SETTLED,
+		/// The invoice expired before it was paid.
+		EXPIRED,
+		FAILED,
+	}
+}
+
+/// Represents a Lightning invoice created to receive XBT.
+#[derive(Debug, Deserialize)]
+pub struct LightningInvoice {
+	#[serde(deserialize_with = "deserialize_id")]
+	pub invoice_id: String,
+	/// The BOLT 11 payment request to share with the payer.
+	pub payment_request: String,
+	/// The amount requested, if the invoice was created for a fixed amount.
+	#[serde(default)]
+	pub amount: Option<Decimal>,
+	/// The amount received so far.
+	#[serde(default)]
+	pub settled_amount: Option<Decimal>,
+	#[serde(default)]
+	pub status: Option<LightningStatus>,
+	/// The UNIX timestamp at which the invoice was created.
+	#[serde(default)]
+	pub created_at: Option<u64>,
+	/// The UNIX timestamp at which the invoice expires.
+	#[serde(default)]
+	pub expires_at: Option<u64>,
+}
+
+/// Contains the result of paying a Lightning invoice.
+#[derive(Debug, Deserialize)]
+pub struct LightningPayment {
+	#[serde(deserialize_with = "deserialize_id")]
+	pub invoice_id: String,
+	#[serde(default)]
+	pub status: Option<LightningStatus>,
+}
+
+/// A builder for the `lightning_receive()` method.
+pub struct LightningReceiveBuilder<'a> {
+	pub(crate) luno_client: &'a LunoClient,
+	pub(crate) url: Url,
+	pub(crate) params: HashMap<&'a str, String>,
+}
+
+impl<'a> LightningReceiveBuilder<'a> {
+	/// Sets the description encoded in the invoice, which is shown to the payer.
+	pub fn with_description(&mut self, description: &str) -> &mut LightningReceiveBuilder<'a> {
+		self.params.insert("description", description.to_owned());
+		self
+	}
+
+	/// Sets the UNIX timestamp at which the invoice expires.
+	pub fn with_expires_at(&mut self, timestamp: u64) -> &mut LightningReceiveBuilder<'a> {
+		self.params.insert("expires_at", timestamp.to_string());
+		self
+	}
+
+	pub async fn post(&mut self) -> Result<LightningInvoice, LunoError> {
+		let url = self.url.clone();
+		self.luno_client.post(url, &self.params).await
+	}
+}
+
+/// A builder for the `lightning_send()` method.
+pub struct LightningSendBuilder<'a> {
+	pub(crate) luno_client: &'a LunoClient,
+	pub(crate) url: Url,
+	pub(crate) params: HashMap<&'a str, String>,
+}
+
+impl<'a> LightningSendBuilder<'a> {
+	/// Sets the description shown in your transaction history.
+	pub fn with_description(&mut self, description: &str) -> &mut LightningSendBuilder<'a> {
+		self.params.insert("description", description.to_owned());
+		self
+	}
+
+	/// Sets a unique ID for the payment, which makes it safe to retry (see `RetryPolicy`).
+	pub fn with_external_id(&mut self, id: &str) -> &mut LightningSendBuilder<'a> {
+		self.params.insert("external_id", id.to_owned());
+		self
+	}
+
+	pub async fn post(&mut self) -> Result<LightningPayment, LunoError> {
+		let url = self.url.clone();
+		self.luno_client
+			.post_maybe_idempotent(url, &self.params, "external_id")
+			.await
+	}
+}
@@ -0,0 +1,65 @@
+/// Declares an enum of codes used by the Luno API along with an `Other` variant
+/// so that codes introduced by Luno after this crate was released still parse.
+///
+/// The generated type implements `FromStr`, `Display`, `Serialize` and `Deserialize`
+/// using the variant names as they appear on the wire.
+macro_rules! string_enum {
+	(
+		$(#[$meta:meta])*
+		pub enum $name:ident {
+			$($(#[$variant_meta:meta])* $variant:ident,)*
+		}
+	) => {
+		$(#[$meta])*
+		#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+		pub enum $name {
+			$($(#[$variant_meta])* $variant,)*
+			/// A code that is not known to this version of the crate.
+			Other(String),
+		}
+
+		impl $name {
+			/// Returns the code as it is used by the Luno API.
+			pub fn as_str(&self) -> &str {
+				match self {
+					$($name::$variant => stringify!($variant),)*
+					$name::Other(code) => code,
+				}
+			}
+
+			fn from_code(code: &str) -> $name {
+				match code {
+					$(stringify!($variant) => $name::$variant,)*
+					other => $name::Other(other.to_owned()),
+				}
+			}
+		}
+
+		impl std::str::FromStr for $name {
+			type Err = std::convert::Infallible;
+
+			fn from_str(s: &str) -> Result<Self, Self::Err> {
+				Ok($name::from_code(s))
+			}
+		}
+
+		impl std::fmt::Display for $name {
+			fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+				f.pad(self.as_str())
+			}
+		}
+
+		impl serde::Serialize for $name {
+			fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				serializer.serialize_str(self.as_str())
+			}
+		}
+
+		impl<'de> serde::Deserialize<'de> for $name {
+			fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+				let code = <std::borrow::Cow<str> as serde::Deserialize>::deserialize(deserializer)?;
+				Ok($name::from_code(&code))
+			}
+		}
+	};
+}
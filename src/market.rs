@@ -1,55 +1,121 @@
-use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
-use strum_macros::{Display, EnumString};
-
-#[derive(EnumString, Display, Debug, Serialize, Deserialize)]
-pub enum Currency {
-	AUD,
-	BCH,
-	BTC,
-	ETH,
-	EUR,
-	GBP,
-	IDR,
-	LTC,
-	MYR,
-	NGN,
-	UGX,
-	XBT,
-	XRP,
-	ZAR,
-	ZMW,
-}
-
-#[derive(EnumString, Display, Debug, Deserialize)]
-pub enum TradingPair {
-	BCHXBT,
-	ETHAUD,
-	ETHEUR,
-	ETHGBP,
-	ETHIDR,
-	ETHMYR,
-	ETHNGN,
-	ETHXBT,
-	ETHZAR,
-	LTCMYR,
-	LTCXBT,
-	LTCNGN,
-	LTCZAR,
-	XBTAUD,
-	XBTEUR,
-	XBTGBP,
-	XBTIDR,
-	XBTMYR,
-	XBTNGN,
-	XBTSGD,
-	XBTUGX,
-	XBTZAR,
-	XBTZMW,
-	XRPMYR,
-	XRPNGN,
-	XRPXBT,
-	XRPZAR,
+use std::collections::BTreeMap;
+
+use reqwest::Url;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::Deserialize;
+
+use crate::{error::LunoError, LimitOrderType, LunoClient, MarketOrderType};
+
+string_enum! {
+	/// Represents a currency or asset on Luno.
+	///
+	/// Currencies that are not known to this crate are represented by `Currency::Other`.
+	pub enum Currency {
+		ADA,
+		AUD,
+		BCH,
+		BTC,
+		DOT,
+		ETH,
+		EUR,
+		GBP,
+		IDR,
+		LINK,
+		LTC,
+		MYR,
+		NGN,
+		SGD,
+		SOL,
+		UGX,
+		UNI,
+		USDC,
+		USDT,
+		XBT,
+		XRP,
+		ZAR,
+		ZMW,
+	}
+}
+
+string_enum! {
+	/// Represents a market on Luno, named by its base currency followed by its counter currency.
+	///
+	/// Markets that are not known to this crate are represented by `TradingPair::Other`.
+	pub enum TradingPair {
+		BCHXBT,
+		ETHAUD,
+		ETHEUR,
+		ETHGBP,
+		ETHIDR,
+		ETHMYR,
+		ETHNGN,
+		ETHUSDC,
+		ETHXBT,
+		ETHZAR,
+		LTCMYR,
+		LTCXBT,
+		LTCNGN,
+		LTCZAR,
+		SOLMYR,
+		SOLNGN,
+		SOLXBT,
+		SOLZAR,
+		USDCMYR,
+		USDCNGN,
+		USDCZAR,
+		USDTMYR,
+		USDTNGN,
+		USDTZAR,
+		XBTAUD,
+		XBTEUR,
+		XBTGBP,
+		XBTIDR,
+		XBTMYR,
+		XBTNGN,
+		XBTSGD,
+		XBTUGX,
+		XBTUSDC,
+		XBTUSDT,
+		XBTZAR,
+		XBTZMW,
+		XRPMYR,
+		XRPNGN,
+		XRPXBT,
+		XRPZAR,
+	}
+}
+
+impl TradingPair {
+	/// Splits the pair into its base and counter currencies.
+	///
+	/// The split is chosen so that as many of the two currencies as possible are
+	/// known to this crate, e.g. `XBTUSDC` splits into `XBT` and `USDC`.
+	pub fn currencies(&self) -> (Currency, Currency) {
+		let code = self.as_str();
+		let is_known = |code: &str| !matches!(Currency::from_code(code), Currency::Other(_));
+		let split = (1..code.len())
+			.filter(|&i| code.is_char_boundary(i))
+			.max_by_key(|&i| {
+				let known = is_known(&code[..i]) as u8 + is_known(&code[i..]) as u8;
+				(known, i == 3)
+			})
+			.unwrap_or(code.len());
+
+		(
+			Currency::from_code(&code[..split]),
+			Currency::from_code(&code[split..]),
+		)
+	}
+
+	/// Returns the base currency of the pair, i.e. the currency being bought or sold.
+	pub fn base(&self) -> Currency {
+		self.currencies().0
+	}
+
+	/// Returns the counter currency of the pair, i.e. the currency used to price the base currency.
+	pub fn counter(&self) -> Currency {
+		self.currencies().1
+	}
 }
 
 #[derive(Debug, Deserialize)]
@@ -86,6 +152,157 @@ pub struct Orderbook {
 	pub timestamp: u64,
 }
 
+/// The estimated outcome of a market order filled against an order book.
+#[derive(Debug, PartialEq)]
+pub struct FillEstimate {
+	/// The base volume bought or sold.
+	pub base_volume: Decimal,
+	/// The counter volume paid or received.
+	pub counter_volume: Decimal,
+	/// The volume-weighted average price of the fill.
+	pub average_price: Decimal,
+	/// How much worse the average price is than the best price in the order book,
+	/// as a fraction of the best price.
+	pub slippage: Decimal,
+}
+
+impl Orderbook {
+	/// Returns the ask with the lowest price.
+	pub fn best_ask(&self) -> Option<&Ask> {
+		self.asks.iter().min_by_key(|ask| ask.price)
+	}
+
+	/// Returns the bid with the highest price.
+	pub fn best_bid(&self) -> Option<&Bid> {
+		self.bids.iter().max_by_key(|bid| bid.price)
+	}
+
+	/// Returns the difference between the best ask price and the best bid price.
+	pub fn spread(&self) -> Option<Decimal> {
+		Some(self.best_ask()?.price - self.best_bid()?.price)
+	}
+
+	/// Returns the price halfway between the best ask price and the best bid price.
+	pub fn mid_price(&self) -> Option<Decimal> {
+		Some((self.best_ask()?.price + self.best_bid()?.price) / Decimal::TWO)
+	}
+
+	/// Returns the total volume of asks priced at or below `price`.
+	pub fn ask_depth_to(&self, price: Decimal) -> Decimal {
+		self.asks
+			.iter()
+			.filter(|ask| ask.price <= price)
+			.map(|ask| ask.volume)
+			.sum()
+	}
+
+	/// Returns the total volume of bids priced at or above `price`.
+	pub fn bid_depth_to(&self, price: Decimal) -> Decimal {
+		self.bids
+			.iter()
+			.filter(|bid| bid.price >= price)
+			.map(|bid| bid.volume)
+			.sum()
+	}
+
+	/// Returns a copy of the order book with the volume of orders at the same price combined,
+	/// such as for the unaggregated order book returned by `get_orderbook()`.
+	pub fn aggregated(&self) -> Orderbook {
+		let asks = aggregate(self.asks.iter().map(|ask| (ask.price, ask.volume)), false)
+			.into_iter()
+			.map(|(price, volume)| Ask { price, volume })
+			.collect();
+		let bids = aggregate(self.bids.iter().map(|bid| (bid.price, bid.volume)), true)
+			.into_iter()
+			.map(|(price, volume)| Bid { price, volume })
+			.collect();
+
+		Orderbook {
+			asks,
+			bids,
+			timestamp: self.timestamp,
+		}
+	}
+
+	/// Estimates the outcome of a market order filled against the order book.
+	///
+	/// As with `market_order()`, `volume` is the counter volume to spend for a `BUY` order
+	/// and the base volume to sell for a `SELL` order.
+	/// Returns `None` if the order book does not have enough volume to fill the order.
+	pub fn estimate_market_order(
+		&self,
+		order_type: &MarketOrderType,
+		volume: Decimal,
+	) -> Option<FillEstimate> {
+		let levels = match order_type {
+			MarketOrderType::BUY => {
+				aggregate(self.asks.iter().map(|ask| (ask.price, ask.volume)), false)
+			}
+			MarketOrderType::SELL => {
+				aggregate(self.bids.iter().map(|bid| (bid.price, bid.volume)), true)
+			}
+		};
+		let best_price = levels.first()?.0;
+
+		let mut remaining = volume;
+		let (mut base_volume, mut counter_volume) = (Decimal::ZERO, Decimal::ZERO);
+		for (price, level_volume) in levels {
+			if remaining <= Decimal::ZERO {
+				break;
+			}
+			let (base, counter) = match order_type {
+				MarketOrderType::BUY => {
+					let counter = remaining.min(price * level_volume);
+					(counter / price, counter)
+				}
+				MarketOrderType::SELL => {
+					let base = remaining.min(level_volume);
+					(base, base * price)
+				}
+			};
+			base_volume += base;
+			counter_volume += counter;
+			remaining -= match order_type {
+				MarketOrderType::BUY => counter,
+				MarketOrderType::SELL => base,
+			};
+		}
+		if remaining > Decimal::ZERO || base_volume.is_zero() {
+			return None;
+		}
+
+		let average_price = counter_volume / base_volume;
+		let slippage = match order_type {
+			MarketOrderType::BUY => (average_price - best_price) / best_price,
+			MarketOrderType::SELL => (best_price - average_price) / best_price,
+		};
+		Some(FillEstimate {
+			base_volume,
+			counter_volume,
+			average_price,
+			slippage,
+		})
+	}
+}
+
+/// Combines the volume of orders at the same price, returning price levels sorted
+/// by price ascending or, if `descending`, by price descending.
+fn aggregate(
+	orders: impl Iterator<Item = (Decimal, Decimal)>,
+	descending: bool,
+) -> Vec<(Decimal, Decimal)> {
+	let mut levels = BTreeMap::new();
+	for (price, volume) in orders {
+		*levels.entry(price).or_insert(Decimal::ZERO) += volume;
+	}
+	let levels = levels.into_iter();
+	if descending {
+		levels.rev().collect()
+	} else {
+		levels.collect()
+	}
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Trade {
 	pub is_buy: bool,
@@ -98,3 +315,397 @@ pub struct Trade {
 pub struct ListTradesResponse {
 	pub trades: Vec<Trade>,
 }
+
+/// The candle durations, in seconds, supported by Luno.
+pub const CANDLE_DURATIONS: [u64; 11] = [
+	60, 300, 900, 1800, 3600, 10800, 14400, 28800, 86400, 259200, 604800,
+];
+
+/// The maximum number of candles returned per call.
+const CANDLES_PAGE_SIZE: usize = 1000;
+
+/// Represents the open, high, low and close prices and the volume traded in a period.
+#[derive(Debug, Deserialize)]
+pub struct Candle {
+	/// The UNIX timestamp of the start of the period, in milliseconds.
+	pub timestamp: u64,
+	pub open: Decimal,
+	pub high: Decimal,
+	pub low: Decimal,
+	pub close: Decimal,
+	pub volume: Decimal,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListCandlesResponse {
+	pub candles: Vec<Candle>,
+	pub duration: u64,
+	pub pair: TradingPair,
+}
+
+/// A builder for the `candles()` method.
+pub struct ListCandlesBuilder<'a> {
+	pub(crate) luno_client: &'a LunoClient,
+	pub(crate) url: Url,
+	pub(crate) since: u64,
+	pub(crate) duration: u64,
+	pub(crate) until: Option<u64>,
+}
+
+impl<'a> ListCandlesBuilder<'a> {
+	/// Only returns candles that start before the given UNIX timestamp, in milliseconds.
+	pub fn until(&mut self, timestamp: u64) -> &mut ListCandlesBuilder<'a> {
+		self.until = Some(timestamp);
+		self
+	}
+
+	/// Executes the query, making as many calls as needed to cover the time range.
+	pub async fn list(&self) -> Result<Vec<Candle>, LunoError> {
+		if !CANDLE_DURATIONS.contains(&self.duration) {
+			return Err(LunoError::InvalidRequest(format!(
+				"unsupported candle duration {}, expected one of {:?}",
+				self.duration, CANDLE_DURATIONS
+			)));
+		}
+
+		let mut candles = Vec::new();
+		let mut since = self.since;
+		loop {
+			if matches!(self.until, Some(until) if since >= until) {
+				break;
+			}
+			let mut url = self.url.clone();
+			url.query_pairs_mut()
+				.append_pair("since", &since.to_string())
+				.append_pair("duration", &self.duration.to_string());
+			let page = self
+				.luno_client
+				.get::<ListCandlesResponse>(url)
+				.await?
+				.candles;
+
+			let page_size = page.len();
+			let next_since = page
+				.last()
+				.map(|candle| candle.timestamp + self.duration * 1000);
+			candles.extend(
+				page.into_iter().filter(
+					|candle| !matches!(self.until, Some(until) if candle.timestamp >= until),
+				),
+			);
+			match next_since {
+				Some(next_since) if page_size >= CANDLES_PAGE_SIZE => since = next_since,
+				_ => break,
+			}
+		}
+		Ok(candles)
+	}
+}
+
+string_enum! {
+	/// Represents the trading status of a market.
+	pub enum TradingStatus {
+		/// Orders can be placed and are matched.
+		ACTIVE,
+		/// Only post-only limit orders can be placed.
+		#[allow(non_camel_case_types)]
+		POST_ONLY,
+		/// Trading is suspended.
+		SUSPENDED,
+	}
+}
+
+/// Represents the limits and precision of a market.
+#[derive(Debug, Deserialize)]
+pub struct MarketInfo {
+	/// The market trading pair.
+	pub market_id: TradingPair,
+	pub trading_status: TradingStatus,
+	pub base_currency: Currency,
+	pub counter_currency: Currency,
+	/// The minimum volume of an order.
+	pub min_volume: Decimal,
+	/// The maximum volume of an order.
+	pub max_volume: Decimal,
+	/// The number of decimal places of the volume of an order.
+	pub volume_scale: u32,
+	/// The minimum price of an order.
+	pub min_price: Decimal,
+	/// The maximum price of an order.
+	pub max_price: Decimal,
+	/// The number of decimal places of the price of an order.
+	pub price_scale: u32,
+	/// The number of decimal places of fees.
+	pub fee_scale: u32,
+}
+
+impl MarketInfo {
+	/// Checks that a limit order can be placed in this market, returning its volume and price
+	/// rounded to the precision of the market.
+	///
+	/// The volume is rounded down. The price of a bid is rounded down and the price of an ask is
+	/// rounded up, so that rounding never gives a worse price than requested.
+	pub fn validate_limit_order(
+		&self,
+		pair: &TradingPair,
+		order_type: &LimitOrderType,
+		volume: Decimal,
+		price: Decimal,
+		post_only: bool,
+	) -> Result<(Decimal, Decimal), LunoError> {
+		if pair != &self.market_id {
+			return Err(LunoError::InvalidRequest(format!(
+				"market info is for {} but the order is for {}",
+				self.market_id, pair
+			)));
+		}
+		match self.trading_status {
+			TradingStatus::ACTIVE => {}
+			TradingStatus::POST_ONLY if post_only => {}
+			TradingStatus::POST_ONLY => {
+				return Err(LunoError::InvalidRequest(format!(
+					"{} only accepts post-only orders",
+					pair
+				)))
+			}
+			_ => {
+				return Err(LunoError::InvalidRequest(format!(
+					"{} is not trading: {}",
+					pair, self.trading_status
+				)))
+			}
+		}
+
+		let volume = volume.round_dp_with_strategy(self.volume_scale, RoundingStrategy::ToZero);
+		let price =
+			match order_type {
+				LimitOrderType::BID => price
+					.round_dp_with_strategy(self.price_scale, RoundingStrategy::ToNegativeInfinity),
+				LimitOrderType::ASK => price
+					.round_dp_with_strategy(self.price_scale, RoundingStrategy::ToPositiveInfinity),
+			};
+
+		if volume < self.min_volume || volume > self.max_volume {
+			return Err(LunoError::InvalidRequest(format!(
+				"volume {} is outside of the range {} to {} for {}",
+				volume, self.min_volume, self.max_volume, pair
+			)));
+		}
+		self.check_price("price", price)?;
+		Ok((volume, price))
+	}
+
+	/// Checks that the stop price of a stop-limit order is within the price limits of this
+	/// market, returning it rounded to the nearest price the market allows.
+	pub fn validate_stop_price(&self, stop_price: Decimal) -> Result<Decimal, LunoError> {
+		let stop_price = stop_price.round_dp(self.price_scale);
+		self.check_price("stop price", stop_price)?;
+		Ok(stop_price)
+	}
+
+	fn check_price(&self, name: &str, price: Decimal) -> Result<(), LunoError> {
+		if price < self.min_price || price > self.max_price {
+			return Err(LunoError::InvalidRequest(format!(
+				"{} {} is outside of the range {} to {} for {}",
+				name, price, self.min_price, self.max_price, self.market_id
+			)));
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListMarketsResponse {
+	pub markets: Vec<MarketInfo>,
+}
+
+#[cfg(test)]
+mod tests {
+	use rust_decimal_macros::dec;
+
+	use super::{Ask, Bid, Currency, MarketInfo, Orderbook, Ticker, TradingPair, TradingStatus};
+	use crate::{error::LunoError, LimitOrderType, MarketOrderType};
+
+	fn orderbook() -> Orderbook {
+		Orderbook {
+			asks: vec![
+				Ask {
+					price: dec!(101),
+					volume: dec!(1),
+				},
+				Ask {
+					price: dec!(102),
+					volume: dec!(2),
+				},
+				Ask {
+					price: dec!(101),
+					volume: dec!(0.5),
+				},
+			],
+			bids: vec![
+				Bid {
+					price: dec!(99),
+					volume: dec!(1),
+				},
+				Bid {
+					price: dec!(100),
+					volume: dec!(1),
+				},
+			],
+			timestamp: 1,
+		}
+	}
+
+	fn market_info() -> MarketInfo {
+		serde_json::from_str(
+			r#"{"market_id":"XBTZAR","trading_status":"ACTIVE","base_currency":"XBT","counter_currency":"ZAR","min_volume":"0.0005","max_volume":"100.0","volume_scale":4,"min_price":"100","max_price":"10000000","price_scale":0,"fee_scale":8}"#,
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn test_unknown_pair() {
+		let ticker: Ticker = serde_json::from_str(
+			r#"{"ask":"2.00","bid":"1.00","last_trade":"1.50","pair":"AVAXZAR","rolling_24_hour_volume":"10.0","timestamp":1}"#,
+		)
+		.unwrap();
+		assert_eq!(ticker.pair, TradingPair::Other("AVAXZAR".into()));
+		assert_eq!(ticker.pair.to_string(), "AVAXZAR");
+		assert_eq!(
+			"XBTZAR".parse::<TradingPair>().unwrap(),
+			TradingPair::XBTZAR
+		);
+	}
+
+	#[test]
+	fn test_currencies() {
+		assert_eq!(
+			TradingPair::XBTZAR.currencies(),
+			(Currency::XBT, Currency::ZAR)
+		);
+		assert_eq!(TradingPair::XBTUSDC.counter(), Currency::USDC);
+		assert_eq!(TradingPair::USDCZAR.base(), Currency::USDC);
+		assert_eq!(
+			TradingPair::Other("AVAXZAR".into()).currencies(),
+			(Currency::Other("AVAX".into()), Currency::ZAR)
+		);
+		assert_eq!(
+			TradingPair::Other("ABCDEF".into()).currencies(),
+			(Currency::Other("ABC".into()), Currency::Other("DEF".into()))
+		);
+	}
+
+	#[test]
+	fn test_validate_limit_order() {
+		let market = market_info();
+		assert_eq!(
+			market
+				.validate_limit_order(
+					&TradingPair::XBTZAR,
+					&LimitOrderType::BID,
+					dec!(0.12345),
+					dec!(500000.7),
+					false
+				)
+				.unwrap(),
+			(dec!(0.1234), dec!(500000))
+		);
+		assert_eq!(
+			market
+				.validate_limit_order(
+					&TradingPair::XBTZAR,
+					&LimitOrderType::ASK,
+					dec!(0.12345),
+					dec!(500000.2),
+					false
+				)
+				.unwrap(),
+			(dec!(0.1234), dec!(500001))
+		);
+	}
+
+	#[test]
+	fn test_validate_stop_price() {
+		let market = market_info();
+		assert_eq!(
+			market.validate_stop_price(dec!(450000.6)).unwrap(),
+			dec!(450001)
+		);
+		assert!(matches!(
+			market.validate_stop_price(dec!(99.4)),
+			Err(LunoError::InvalidRequest(_))
+		));
+		assert!(matches!(
+			market.validate_stop_price(dec!(10000001)),
+			Err(LunoError::InvalidRequest(_))
+		));
+	}
+
+	#[test]
+	fn test_validate_limit_order_limits() {
+		let mut market = market_info();
+		let validate = |market: &MarketInfo, pair, volume, post_only| {
+			market.validate_limit_order(
+				&pair,
+				&LimitOrderType::BID,
+				volume,
+				dec!(500000),
+				post_only,
+			)
+		};
+		assert!(matches!(
+			validate(&market, TradingPair::XBTZAR, dec!(0.00049), false),
+			Err(LunoError::InvalidRequest(_))
+		));
+		assert!(matches!(
+			validate(&market, TradingPair::ETHZAR, dec!(1), false),
+			Err(LunoError::InvalidRequest(_))
+		));
+
+		market.trading_status = TradingStatus::POST_ONLY;
+		assert!(validate(&market, TradingPair::XBTZAR, dec!(1), false).is_err());
+		assert!(validate(&market, TradingPair::XBTZAR, dec!(1), true).is_ok());
+	}
+
+	#[test]
+	fn test_orderbook_prices() {
+		let orderbook = orderbook();
+		assert_eq!(orderbook.best_ask().unwrap().price, dec!(101));
+		assert_eq!(orderbook.best_bid().unwrap().price, dec!(100));
+		assert_eq!(orderbook.spread(), Some(dec!(1)));
+		assert_eq!(orderbook.mid_price(), Some(dec!(100.5)));
+		assert_eq!(orderbook.ask_depth_to(dec!(101)), dec!(1.5));
+		assert_eq!(orderbook.bid_depth_to(dec!(99)), dec!(2));
+	}
+
+	#[test]
+	fn test_orderbook_aggregated() {
+		let orderbook = orderbook().aggregated();
+		let asks: Vec<_> = orderbook.asks.iter().map(|a| (a.price, a.volume)).collect();
+		assert_eq!(asks, vec![(dec!(101), dec!(1.5)), (dec!(102), dec!(2))]);
+		let bids: Vec<_> = orderbook.bids.iter().map(|b| (b.price, b.volume)).collect();
+		assert_eq!(bids, vec![(dec!(100), dec!(1)), (dec!(99), dec!(1))]);
+	}
+
+	#[test]
+	fn test_estimate_market_order() {
+		let orderbook = orderbook();
+		let buy = orderbook
+			.estimate_market_order(&MarketOrderType::BUY, dec!(253.5))
+			.unwrap();
+		assert_eq!(buy.base_volume, dec!(2.5));
+		assert_eq!(buy.counter_volume, dec!(253.5));
+		assert_eq!(buy.average_price, dec!(101.4));
+
+		let sell = orderbook
+			.estimate_market_order(&MarketOrderType::SELL, dec!(2))
+			.unwrap();
+		assert_eq!(sell.counter_volume, dec!(199));
+		assert_eq!(sell.average_price, dec!(99.5));
+		assert_eq!(sell.slippage, dec!(0.005));
+
+		assert!(orderbook
+			.estimate_market_order(&MarketOrderType::SELL, dec!(3))
+			.is_none());
+	}
+}
@@ -1,12 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
+use futures::future::{self, Either};
+use futures::{pin_mut, stream, Stream, StreamExt};
 use reqwest::Url;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumString};
 
-use crate::TradingPair;
-use crate::{error::LunoError, LunoClient};
+use crate::de::deserialize_optional_id;
+use crate::error::{ErrorCode, LunoError};
+use crate::{LunoClient, MarketInfo, TradingPair, UserEvent, UserStream};
+
+/// The largest number of orders Luno returns in a single page.
+const ORDERS_PAGE_SIZE: u64 = 1000;
+
+/// The default interval between requests made by an `OrderHandle` to check on its order.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Represents the type of the limit order.
 #[derive(EnumString, Display, Deserialize, Serialize, Debug)]
@@ -16,18 +26,36 @@ pub enum LimitOrderType {
 }
 
 /// Represents the type of the market order.
-#[derive(EnumString, Display, Deserialize, Debug)]
+#[derive(EnumString, Display, Deserialize, Debug, Clone, PartialEq)]
 pub enum MarketOrderType {
 	BUY,
 	SELL,
 }
 
+string_enum! {
+	/// Represents the type of an order.
+	///
+	/// Version 1 of the API reports the side of the order as the type (`BID` or `ASK`),
+	/// while later versions report how the order was placed and report the side separately.
+	pub enum OrderType {
+		ASK,
+		BID,
+		LIMIT,
+		MARKET,
+		#[allow(non_camel_case_types)]
+		STOP_LIMIT,
+	}
+}
+
 /// Represents an order.
-#[derive(Debug, Deserialize)]
+///
+/// Orders returned by versions 2 and 3 of the API have more fields, which are `None` for orders
+/// returned by version 1.
+#[derive(Debug, Clone, Deserialize)]
 pub struct Order {
-	/// The base account ID against which this order is made.
+	/// The amount of base filled.
 	pub base: Decimal,
-	/// The counter account ID against which this order is made.
+	/// The amount of counter filled.
 	pub counter: Decimal,
 	/// The UNIX timestamp of creation of the order.
 	pub creation_timestamp: u64,
@@ -45,16 +73,39 @@ pub struct Order {
 	pub limit_volume: Decimal,
 	/// The ID of the order.
 	pub order_id: String,
+	/// The client order ID of the order, if one was given when it was placed.
+	#[serde(default)]
+	pub client_order_id: Option<String>,
 	/// The market trading pair.
 	pub pair: TradingPair,
-	/// The state of the order.
+	/// The state of the order. Later versions of the API report this as `status`.
+	#[serde(alias = "status")]
 	pub state: OrderState,
 	/// The type of the order.
 	#[serde(alias = "type")]
-	pub order_type: LimitOrderType,
+	pub order_type: OrderType,
+	/// The side of the order. Only reported by later versions of the API.
+	#[serde(default)]
+	pub side: Option<MarketOrderType>,
+	/// How long the order remains on the order book. Only reported by later versions of the API.
+	#[serde(default)]
+	pub time_in_force: Option<TimeInForce>,
+	/// The trigger price of a stop-limit order.
+	#[serde(default)]
+	pub stop_price: Option<Decimal>,
+	/// The side of the trigger price of a stop-limit order.
+	#[serde(default)]
+	pub stop_direction: Option<StopDirection>,
+	/// The ID of the base account against which this order is made.
+	#[serde(default, deserialize_with = "deserialize_optional_id")]
+	pub base_account_id: Option<String>,
+	/// The ID of the counter account against which this order is made.
+	#[serde(default, deserialize_with = "deserialize_optional_id")]
+	pub counter_account_id: Option<String>,
 }
 
 /// A builder for the `list_orders()` method.
+#[derive(Clone)]
 pub struct ListOrdersBuilder<'a> {
 	pub(crate) state: Option<OrderState>,
 	pub(crate) pair: Option<TradingPair>,
@@ -109,6 +160,74 @@ impl<'a> ListOrdersBuilder<'a> {
 			.await?
 			.orders)
 	}
+
+	/// Returns a stream of all the orders matching the query, newest first, fetching further
+	/// pages as needed.
+	///
+	/// Each page is fetched with `created_before` set just after the oldest order seen so far,
+	/// so that orders created in the same millisecond are not skipped, and orders that were
+	/// already returned are skipped instead. The page size is set with `filter_limit()`, and
+	/// defaults to the largest page Luno allows. The stream ends after the last page or the
+	/// first error.
+	///
+	/// If a whole page of orders was created in the same millisecond, the orders after it cannot
+	/// be fetched without skipping some, so the stream ends with `LunoError::PageFull` instead.
+	pub fn stream(&self) -> impl Stream<Item = Result<Order, LunoError>> + 'a {
+		let mut builder = self.clone();
+		let page_size = *builder.limit.get_or_insert(ORDERS_PAGE_SIZE);
+		// `cursor` is the `created_before` the stream itself moved on to, if any.
+		let pages = (Some(builder), None, HashSet::new(), Vec::new().into_iter());
+
+		stream::unfold(
+			pages,
+			move |(mut next, mut cursor, mut seen, mut orders)| async move {
+				loop {
+					if let Some(order) = orders.next() {
+						return Some((order, (next, cursor, seen, orders)));
+					}
+
+					let mut builder = next.take()?;
+					let page = match builder.list().await {
+						Ok(page) => page.unwrap_or_default(),
+						Err(e) => return Some((Err(e), (None, cursor, seen, orders))),
+					};
+					let oldest = match page.iter().map(|order| order.creation_timestamp).min() {
+						Some(oldest) => oldest,
+						None => continue,
+					};
+					let full = page.len() as u64 >= page_size;
+					let resumed = cursor == Some(oldest + 1);
+
+					let mut page: Vec<Result<Order, LunoError>> = page
+						.into_iter()
+						.filter(|order| !seen.contains(&order.order_id))
+						.map(Ok)
+						.collect();
+					// Only orders created at the oldest timestamp can be returned again.
+					if !resumed {
+						seen.clear();
+					}
+					seen.extend(
+						page.iter()
+							.flatten()
+							.filter(|order| order.creation_timestamp == oldest)
+							.map(|order| order.order_id.clone()),
+					);
+
+					if full && resumed {
+						// The whole page was created in the same millisecond, so there may be more
+						// orders at that timestamp than can be fetched in one page.
+						page.push(Err(LunoError::PageFull { timestamp: oldest }));
+					} else if full {
+						cursor = Some(oldest + 1);
+						builder.created_before = cursor;
+						next = Some(builder);
+					}
+					orders = page.into_iter();
+				}
+			},
+		)
+	}
 }
 
 /// Contains a list of orders.
@@ -145,15 +264,26 @@ pub struct CancelOrderResponse {
 	pub success: bool,
 }
 
+/// The outcome of cancelling one of the orders cancelled by `cancel_all_orders()`.
+#[derive(Debug)]
+pub struct CancelledOrder {
+	/// The ID of the order.
+	pub order_id: String,
+	/// The response to the request to cancel the order.
+	pub result: Result<CancelOrderResponse, LunoError>,
+}
+
 /// Represents the state of an order.
-#[derive(EnumString, Display, Debug, Deserialize)]
+#[derive(EnumString, Display, Debug, Clone, PartialEq, Deserialize)]
 pub enum OrderState {
+	/// The order is waiting to be triggered, e.g. a stop order.
+	AWAITING,
 	COMPLETE,
 	PENDING,
 }
 
 /// Side of the trigger (stop) price to activate the order.
-#[derive(EnumString, Display, Debug, Serialize)]
+#[derive(EnumString, Display, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum StopDirection {
 	BELOW,
 	ABOVE,
@@ -165,10 +295,29 @@ pub enum StopDirection {
 	RELATIVE_LAST_TRADE,
 }
 
+/// How long a limit order remains on the order book before it is expired.
+#[derive(EnumString, Display, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TimeInForce {
+	/// Good 'Til Cancelled. The order remains open until it is filled or cancelled.
+	GTC,
+	/// Immediate Or Cancel. The part of the order that cannot be filled
+	/// immediately is cancelled.
+	IOC,
+	/// Fill Or Kill. The order is cancelled unless it can be filled
+	/// immediately in full.
+	FOK,
+}
+
 /// A builder for the `limit_order()` method.
 pub struct PostLimitOrderBuilder<'a> {
 	pub(crate) luno_client: &'a LunoClient,
 	pub(crate) url: Url,
+	pub(crate) pair: TradingPair,
+	pub(crate) order_type: LimitOrderType,
+	pub(crate) volume: Decimal,
+	pub(crate) price: Decimal,
+	pub(crate) stop_price: Option<Decimal>,
+	pub(crate) market: Option<&'a MarketInfo>,
 	pub(crate) params: HashMap<&'a str, String>,
 }
 
@@ -189,7 +338,7 @@ impl<'a> PostLimitOrderBuilder<'a> {
 	}
 
 	pub fn with_stop_price(&mut self, price: Decimal) -> &mut PostLimitOrderBuilder<'a> {
-		self.params.insert("stop_price", price.to_string());
+		self.stop_price = Some(price);
 		self
 	}
 
@@ -201,22 +350,108 @@ impl<'a> PostLimitOrderBuilder<'a> {
 		self
 	}
 
+	/// Sets how long the order remains on the order book. Defaults to `TimeInForce::GTC`.
+	///
+	/// Post-only orders must be `TimeInForce::GTC`, since they never trade immediately.
+	pub fn with_time_in_force(
+		&mut self,
+		time_in_force: TimeInForce,
+	) -> &mut PostLimitOrderBuilder<'a> {
+		self.params
+			.insert("time_in_force", time_in_force.to_string());
+		self
+	}
+
+	/// Sets a client order ID, which makes the order safe to retry (see `RetryPolicy`).
+	pub fn with_client_order_id(&mut self, id: &str) -> &mut PostLimitOrderBuilder<'a> {
+		self.params.insert("client_order_id", id.to_owned());
+		self
+	}
+
+	/// Sets a randomly generated client order ID, which can be read with `client_order_id()`.
+	pub fn with_generated_client_order_id(&mut self) -> &mut PostLimitOrderBuilder<'a> {
+		self.params
+			.insert("client_order_id", generate_client_order_id());
+		self
+	}
+
+	/// Returns the client order ID of the order, if one was set.
+	pub fn client_order_id(&self) -> Option<&str> {
+		self.params.get("client_order_id").map(String::as_str)
+	}
+
+	/// Validates the order against the limits of the market before it is posted,
+	/// rounding the volume, price and stop price to the precision of the market.
+	///
+	/// The market info can be retrieved with `list_markets()`.
+	pub fn with_market_info(&mut self, market: &'a MarketInfo) -> &mut PostLimitOrderBuilder<'a> {
+		self.market = Some(market);
+		self
+	}
+
 	pub async fn post(&mut self) -> Result<PostOrderResponse, LunoError> {
 		let url = self.url.clone();
+		if self.params.contains_key("post_only") {
+			match self.params.get("time_in_force").map(String::as_str) {
+				None | Some("GTC") => {}
+				Some(time_in_force) => {
+					return Err(LunoError::InvalidRequest(format!(
+						"post-only orders cannot use time in force {}",
+						time_in_force
+					)))
+				}
+			}
+		}
+		let (volume, price, stop_price) = match self.market {
+			Some(market) => {
+				let (volume, price) = market.validate_limit_order(
+					&self.pair,
+					&self.order_type,
+					self.volume,
+					self.price,
+					self.params.contains_key("post_only"),
+				)?;
+				let stop_price = match self.stop_price {
+					Some(stop_price) => Some(market.validate_stop_price(stop_price)?),
+					None => None,
+				};
+				(volume, price, stop_price)
+			}
+			None => (self.volume, self.price, self.stop_price),
+		};
 
-		Ok(self
-			.luno_client
-			.http
-			.post(url)
-			.basic_auth(
-				self.luno_client.credentials.key.to_owned(),
-				Some(self.luno_client.credentials.secret.to_owned()),
-			)
-			.form(&self.params)
-			.send()
-			.await?
-			.json()
-			.await?)
+		let mut params = self.params.clone();
+		params.insert("pair", self.pair.to_string());
+		params.insert("type", self.order_type.to_string());
+		params.insert("volume", volume.to_string());
+		params.insert("price", price.to_string());
+		if let Some(stop_price) = stop_price {
+			params.insert("stop_price", stop_price.to_string());
+		}
+		self.luno_client
+			.post_maybe_idempotent(url, &params, "client_order_id")
+			.await
+	}
+
+	/// Posts the order and, if it is unclear whether the order was placed, e.g. because the
+	/// request timed out, looks it up by its client order ID before posting it again.
+	///
+	/// A client order ID is generated if one has not been set.
+	pub async fn post_or_recover(&mut self) -> Result<PostOrderResponse, LunoError> {
+		if self.client_order_id().is_none() {
+			self.with_generated_client_order_id();
+		}
+		let client_order_id = self.client_order_id().unwrap_or_default().to_owned();
+
+		match self.post().await {
+			Err(e) if is_ambiguous(&e) => {
+				match recover(self.luno_client, &client_order_id).await? {
+					Some(response) => Ok(response),
+					None => self.post().await,
+				}
+			}
+			result => result,
+		}
 	}
 }
 
@@ -238,20 +473,351 @@ impl<'a> PostMarketOrderBuilder<'a> {
 		self
 	}
 
+	/// Sets a client order ID, which makes the order safe to retry (see `RetryPolicy`).
+	pub fn with_client_order_id(&mut self, id: &str) -> &mut PostMarketOrderBuilder<'a> {
+		self.params.insert("client_order_id", id.to_owned());
+		self
+	}
+
+	/// Sets a randomly generated client order ID, which can be read with `client_order_id()`.
+	pub fn with_generated_client_order_id(&mut self) -> &mut PostMarketOrderBuilder<'a> {
+		self.params
+			.insert("client_order_id", generate_client_order_id());
+		self
+	}
+
+	/// Returns the client order ID of the order, if one was set.
+	pub fn client_order_id(&self) -> Option<&str> {
+		self.params.get("client_order_id").map(String::as_str)
+	}
+
 	pub async fn post(&mut self) -> Result<PostOrderResponse, LunoError> {
 		let url = self.url.clone();
-		Ok(self
-			.luno_client
-			.http
-			.post(url)
-			.basic_auth(
-				self.luno_client.credentials.key.to_owned(),
-				Some(self.luno_client.credentials.secret.to_owned()),
-			)
-			.form(&self.params)
-			.send()
-			.await?
-			.json()
-			.await?)
+		self.luno_client
+			.post_maybe_idempotent(url, &self.params, "client_order_id")
+			.await
+	}
+
+	/// Posts the order and, if it is unclear whether the order was placed, e.g. because the
+	/// request timed out, looks it up by its client order ID before posting it again.
+	///
+	/// A client order ID is generated if one has not been set.
+	pub async fn post_or_recover(&mut self) -> Result<PostOrderResponse, LunoError> {
+		if self.client_order_id().is_none() {
+			self.with_generated_client_order_id();
+		}
+		let client_order_id = self.client_order_id().unwrap_or_default().to_owned();
+
+		match self.post().await {
+			Err(e) if is_ambiguous(&e) => {
+				match recover(self.luno_client, &client_order_id).await? {
+					Some(response) => Ok(response),
+					None => self.post().await,
+				}
+			}
+			result => result,
+		}
+	}
+}
+
+/// Tracks a placed order as it is filled, created with `LunoClient::track_order()`.
+///
+/// The order is polled with `get_order()`. If a user stream is given with `with_user_stream()`,
+/// the order is also fetched as soon as the stream reports a change to it.
+pub struct OrderHandle<'a> {
+	pub(crate) luno_client: &'a LunoClient,
+	pub(crate) order_id: String,
+	pub(crate) poll_interval: Duration,
+	pub(crate) user_stream: Option<UserStream>,
+	pub(crate) order: Option<Order>,
+}
+
+impl<'a> OrderHandle<'a> {
+	pub(crate) fn new(luno_client: &'a LunoClient, order_id: &str) -> OrderHandle<'a> {
+		OrderHandle {
+			luno_client,
+			order_id: order_id.to_owned(),
+			poll_interval: DEFAULT_POLL_INTERVAL,
+			user_stream: None,
+			order: None,
+		}
+	}
+
+	/// Sets the interval between requests to check on the order. Defaults to one second.
+	pub fn with_poll_interval(&mut self, interval: Duration) -> &mut OrderHandle<'a> {
+		self.poll_interval = interval;
+		self
+	}
+
+	/// Fetches the order as soon as `stream` reports a change to it, rather than waiting
+	/// for the next poll.
+	///
+	/// Connect the stream before posting the order so that no events are missed. Errors from the
+	/// stream are ignored while it reconnects, but waiting fails if the stream ends.
+	pub fn with_user_stream(&mut self, stream: UserStream) -> &mut OrderHandle<'a> {
+		self.user_stream = Some(stream);
+		self
+	}
+
+	/// Returns the ID of the tracked order.
+	pub fn order_id(&self) -> &str {
+		&self.order_id
+	}
+
+	/// Returns the order as it was last fetched, if it has been fetched.
+	pub fn order(&self) -> Option<&Order> {
+		self.order.as_ref()
+	}
+
+	/// Fetches the order.
+	pub async fn refresh(&mut self) -> Result<&Order, LunoError> {
+		let order = self.luno_client.get_order(&self.order_id).await?;
+		Ok(self.order.insert(order))
+	}
+
+	/// Waits until the order changes state or is filled further, and returns it.
+	///
+	/// Returns the order straight away if it has not been fetched yet.
+	pub async fn next_update(&mut self) -> Result<&Order, LunoError> {
+		let previous = match &self.order {
+			Some(order) => Progress::of(order),
+			None => return self.refresh().await,
+		};
+		loop {
+			self.wait_for_change().await?;
+			let order = self.luno_client.get_order(&self.order_id).await?;
+			if Progress::of(&order) != previous {
+				return Ok(self.order.insert(order));
+			}
+		}
+	}
+
+	/// Waits until the order is complete, returning `LunoError::Timeout` if it is not
+	/// complete within `timeout`.
+	///
+	/// Returns the order straight away if it was already complete when last fetched.
+	pub async fn wait(&mut self, timeout: Duration) -> Result<Order, LunoError> {
+		if let Some(order) = self
+			.order
+			.as_ref()
+			.filter(|o| o.state == OrderState::COMPLETE)
+		{
+			return Ok(order.clone());
+		}
+		let completion = async {
+			loop {
+				let order = self.next_update().await?;
+				if order.state == OrderState::COMPLETE {
+					return Ok(order.clone());
+				}
+			}
+		};
+		tokio::time::timeout(timeout, completion)
+			.await
+			.unwrap_or(Err(LunoError::Timeout))
+	}
+
+	/// Waits until the order is complete and cancels it if it is not complete within `timeout`,
+	/// returning the order as fetched after cancelling it.
+	pub async fn wait_or_cancel(&mut self, timeout: Duration) -> Result<Order, LunoError> {
+		match self.wait(timeout).await {
+			Err(LunoError::Timeout) => {
+				self.luno_client.cancel_order(&self.order_id).await?;
+				Ok(self.refresh().await?.clone())
+			}
+			result => result,
+		}
+	}
+
+	/// Waits for the next poll, or until the user stream reports a change to the order.
+	async fn wait_for_change(&mut self) -> Result<(), LunoError> {
+		let sleep = tokio::time::sleep(self.poll_interval);
+		let (order_id, stream) = match &mut self.user_stream {
+			Some(stream) => (&self.order_id, stream),
+			None => {
+				sleep.await;
+				return Ok(());
+			}
+		};
+
+		let event = async {
+			loop {
+				match stream.next().await {
+					Some(Ok(UserEvent::OrderStatus(update))) if &update.order_id == order_id => {
+						return Ok(())
+					}
+					Some(Ok(UserEvent::OrderFill(update))) if &update.order_id == order_id => {
+						return Ok(())
+					}
+					// The stream reconnects after an error, and the order is polled meanwhile.
+					Some(Ok(_)) | Some(Err(_)) => {}
+					None => return Err(LunoError::StreamClosed),
+				}
+			}
+		};
+		pin_mut!(sleep, event);
+		match future::select(event, sleep).await {
+			Either::Left((result, _)) => result,
+			Either::Right(_) => Ok(()),
+		}
+	}
+}
+
+/// The parts of an order that change as it is filled.
+#[derive(PartialEq)]
+struct Progress {
+	state: OrderState,
+	base: Decimal,
+	counter: Decimal,
+	fee_base: Decimal,
+	fee_counter: Decimal,
+}
+
+impl Progress {
+	fn of(order: &Order) -> Progress {
+		Progress {
+			state: order.state.clone(),
+			base: order.base,
+			counter: order.counter,
+			fee_base: order.fee_base,
+			fee_counter: order.fee_counter,
+		}
+	}
+}
+
+/// Generates a random client order ID.
+fn generate_client_order_id() -> String {
+	format!("{:032x}", rand::random::<u128>())
+}
+
+/// Returns whether an order may have been placed even though posting it failed with `error`.
+fn is_ambiguous(error: &LunoError) -> bool {
+	match error {
+		LunoError::HttpError(_) => true,
+		LunoError::ApiError {
+			code: ErrorCode::ErrDuplicateClientOrderID,
+			..
+		} => true,
+		e => e.status().is_some_and(|status| status.is_server_error()),
+	}
+}
+
+/// Looks up an order by its client order ID after an ambiguous failure to post it,
+/// returning `None` if the order was not placed.
+async fn recover(
+	luno_client: &LunoClient,
+	client_order_id: &str,
+) -> Result<Option<PostOrderResponse>, LunoError> {
+	match luno_client.get_order_by_client_id(client_order_id).await {
+		Ok(order) => Ok(Some(PostOrderResponse {
+			order_id: Some(order.order_id),
+			error: None,
+		})),
+		Err(LunoError::ApiError {
+			code: ErrorCode::ErrOrderNotFound,
+			..
+		}) => Ok(None),
+		Err(e) => Err(e),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rust_decimal_macros::dec;
+
+	use super::{MarketOrderType, Order, OrderState, OrderType, StopDirection, TimeInForce};
+
+	#[test]
+	fn test_deserialize_v1_order() {
+		let order: Order = serde_json::from_str(
+			r#"{
+				"order_id": "BXMC2CJ7HNB88U4",
+				"creation_timestamp": 1367849297609,
+				"expiration_timestamp": 1367935697609,
+				"completed_timestamp": 0,
+				"type": "ASK",
+				"state": "PENDING",
+				"limit_price": "1000.00",
+				"limit_volume": "0.80",
+				"base": "0.40",
+				"counter": "400.00",
+				"fee_base": "0.00",
+				"fee_counter": "4.00",
+				"pair": "XBTZAR"
+			}"#,
+		)
+		.unwrap();
+		assert_eq!(order.order_type, OrderType::ASK);
+		assert_eq!(order.state, OrderState::PENDING);
+		assert_eq!(order.base, dec!(0.40));
+		assert!(order.side.is_none());
+		assert!(order.base_account_id.is_none());
+	}
+
+	#[test]
+	fn test_deserialize_v2_order() {
+		let order: Order = serde_json::from_str(
+			r#"{
+				"order_id": "BXMC2CJ7HNB88U4",
+				"client_order_id": "my-order-1",
+				"creation_timestamp": 1367849297609,
+				"expiration_timestamp": 0,
+				"completed_timestamp": 1367849297800,
+				"type": "LIMIT",
+				"side": "SELL",
+				"status": "COMPLETE",
+				"time_in_force": "IOC",
+				"limit_price": "1000.00",
+				"limit_volume": "0.80",
+				"base": "0.80",
+				"counter": "800.00",
+				"fee_base": "0.00",
+				"fee_counter": "8.00",
+				"pair": "XBTZAR",
+				"base_account_id": 12345,
+				"counter_account_id": 67890
+			}"#,
+		)
+		.unwrap();
+		assert_eq!(order.order_type, OrderType::LIMIT);
+		assert_eq!(order.side, Some(MarketOrderType::SELL));
+		assert_eq!(order.state, OrderState::COMPLETE);
+		assert_eq!(order.time_in_force, Some(TimeInForce::IOC));
+		assert_eq!(order.base_account_id.as_deref(), Some("12345"));
+		assert_eq!(order.counter_account_id.as_deref(), Some("67890"));
+	}
+
+	#[test]
+	fn test_deserialize_v3_stop_order() {
+		let order: Order = serde_json::from_str(
+			r#"{
+				"order_id": "BXMC2CJ7HNB88U4",
+				"client_order_id": "my-order-2",
+				"creation_timestamp": 1367849297609,
+				"expiration_timestamp": 0,
+				"completed_timestamp": 0,
+				"type": "STOP_LIMIT",
+				"side": "BUY",
+				"status": "AWAITING",
+				"time_in_force": "GTC",
+				"stop_price": "1100.00",
+				"stop_direction": "ABOVE",
+				"limit_price": "1200.00",
+				"limit_volume": "0.50",
+				"base": "0.00",
+				"counter": "0.00",
+				"fee_base": "0.00",
+				"fee_counter": "0.00",
+				"pair": "XBTZAR",
+				"base_account_id": "12345",
+				"counter_account_id": "67890"
+			}"#,
+		)
+		.unwrap();
+		assert_eq!(order.order_type, OrderType::STOP_LIMIT);
+		assert_eq!(order.state, OrderState::AWAITING);
+		assert_eq!(order.stop_price, Some(dec!(1100.00)));
+		assert_eq!(order.stop_direction, Some(StopDirection::ABOVE));
+		assert_eq!(order.base_account_id.as_deref(), Some("12345"));
 	}
 }
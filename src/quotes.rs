@@ -40,18 +40,6 @@ impl<'a> CreateQuoteBuilder<'a> {
 	pub async fn post(&mut self) -> Result<Quote, LunoError> {
 		let url = self.url.clone();
 
-		Ok(self
-			.luno_client
-			.http
-			.post(url)
-			.basic_auth(
-				self.luno_client.credentials.key.to_owned(),
-				Some(self.luno_client.credentials.secret.to_owned()),
-			)
-			.form(&self.params)
-			.send()
-			.await?
-			.json()
-			.await?)
+		self.luno_client.post(url, &self.params).await
 	}
 }
@@ -0,0 +1,103 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::{sleep, Instant};
+
+/// A token bucket shared by all requests made through a `LunoClient`.
+///
+/// The bucket starts full and refills continuously, so bursts of up to `capacity`
+/// requests are sent immediately while the sustained rate stays within the budget.
+pub(crate) struct RateLimiter {
+	capacity: f64,
+	refill_per_sec: f64,
+	state: Mutex<BucketState>,
+}
+
+struct BucketState {
+	tokens: f64,
+	last_refill: Instant,
+	paused_until: Option<Instant>,
+}
+
+impl RateLimiter {
+	/// Creates a limiter that allows `requests` requests every `per`.
+	pub(crate) fn new(requests: u32, per: Duration) -> RateLimiter {
+		let capacity = f64::from(requests.max(1));
+		RateLimiter {
+			capacity,
+			refill_per_sec: capacity / per.as_secs_f64(),
+			state: Mutex::new(BucketState {
+				tokens: capacity,
+				last_refill: Instant::now(),
+				paused_until: None,
+			}),
+		}
+	}
+
+	/// Waits until a request may be sent and takes a token for it.
+	pub(crate) async fn acquire(&self) {
+		loop {
+			let wait = {
+				let mut state = self.state.lock().unwrap();
+				let now = Instant::now();
+				self.refill(&mut state, now);
+
+				match state.paused_until {
+					Some(until) if until > now => until - now,
+					_ if state.tokens >= 1.0 => {
+						state.tokens -= 1.0;
+						return;
+					}
+					_ => Duration::from_secs_f64((1.0 - state.tokens) / self.refill_per_sec),
+				}
+			};
+			sleep(wait).await;
+		}
+	}
+
+	/// Stops requests from being sent for `duration`, e.g. after Luno responded with
+	/// `429 Too Many Requests`.
+	pub(crate) fn pause_for(&self, duration: Duration) {
+		let mut state = self.state.lock().unwrap();
+		let until = Instant::now() + duration;
+		state.tokens = 0.0;
+		state.paused_until = Some(state.paused_until.map_or(until, |paused| paused.max(until)));
+	}
+
+	fn refill(&self, state: &mut BucketState, now: Instant) {
+		let elapsed = now.saturating_duration_since(state.last_refill);
+		state.tokens =
+			(state.tokens + elapsed.as_secs_f64() * self.refill_per_sec).min(self.capacity);
+		state.last_refill = now;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use tokio::time::Instant;
+
+	use super::RateLimiter;
+
+	#[tokio::test(start_paused = true)]
+	async fn test_acquire_waits_for_refill() {
+		let limiter = RateLimiter::new(2, Duration::from_secs(1));
+		let start = Instant::now();
+		limiter.acquire().await;
+		limiter.acquire().await;
+		assert_eq!(start.elapsed(), Duration::from_secs(0));
+
+		limiter.acquire().await;
+		assert!(start.elapsed() >= Duration::from_millis(500));
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_pause_for() {
+		let limiter = RateLimiter::new(300, Duration::from_secs(60));
+		let start = Instant::now();
+		limiter.pause_for(Duration::from_secs(3));
+		limiter.acquire().await;
+		assert!(start.elapsed() >= Duration::from_secs(3));
+	}
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::StatusCode;
+
+use crate::error::LunoError;
+
+/// Describes how failed requests are retried by a `LunoClient`.
+///
+/// Only idempotent requests are retried, such as `get_ticker()`, `list_transactions()` or
+/// `get_order()`. Placing orders and sending funds is only retried when the request has a
+/// client order ID or external ID, which stops Luno from acting on it twice, or when Luno
+/// rejected the request because of rate limiting, in which case nothing was done.
+///
+/// If the response to such a request is lost, e.g. to a timeout, the request may have gone
+/// through even though it failed. Its retry then reuses an ID that Luno has already seen, and
+/// fails with an error such as `ErrorCode::ErrDuplicateClientOrderID`. After an error like this,
+/// look the request up by its ID, e.g. with `get_order_by_client_id()`, to find out whether it
+/// went through.
+///
+/// A policy can be set with `LunoClientBuilder::with_retry_policy()`.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+	/// The maximum number of attempts made for a request, including the first one.
+	pub max_attempts: u32,
+	/// The upper bound of the delay before the first retry.
+	/// The bound doubles with each subsequent retry.
+	pub initial_backoff: Duration,
+	/// The largest upper bound of the delay between attempts.
+	pub max_backoff: Duration,
+	/// The HTTP statuses of responses that are retried.
+	pub retryable_statuses: Vec<StatusCode>,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		RetryPolicy {
+			max_attempts: 3,
+			initial_backoff: Duration::from_millis(200),
+			max_backoff: Duration::from_secs(5),
+			retryable_statuses: vec![
+				StatusCode::INTERNAL_SERVER_ERROR,
+				StatusCode::BAD_GATEWAY,
+				StatusCode::SERVICE_UNAVAILABLE,
+				StatusCode::GATEWAY_TIMEOUT,
+			],
+		}
+	}
+}
+
+impl RetryPolicy {
+	/// Returns whether a request that failed with `error` should be attempted again.
+	pub(crate) fn is_retryable(&self, error: &LunoError) -> bool {
+		match error {
+			LunoError::HttpError(e) => {
+				e.is_connect() || e.is_timeout() || e.is_request() || e.is_body()
+			}
+			LunoError::ApiError { status, .. } | LunoError::UnexpectedResponse { status, .. } => {
+				self.retryable_statuses.contains(status)
+			}
+			_ => false,
+		}
+	}
+
+	/// Returns a random delay before making attempt number `attempt + 1`,
+	/// bounded by an exponentially growing ceiling.
+	pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+		let ceiling = self
+			.initial_backoff
+			.checked_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+			.map_or(self.max_backoff, |ceiling| ceiling.min(self.max_backoff));
+		ceiling.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use reqwest::StatusCode;
+
+	use super::RetryPolicy;
+	use crate::error::LunoError;
+
+	#[test]
+	fn test_backoff_bounds() {
+		let policy = RetryPolicy {
+			initial_backoff: Duration::from_millis(100),
+			max_backoff: Duration::from_millis(300),
+			..RetryPolicy::default()
+		};
+		for _ in 0..100 {
+			assert!(policy.backoff(1) <= Duration::from_millis(100));
+			assert!(policy.backoff(2) <= Duration::from_millis(200));
+			assert!(policy.backoff(3) <= Duration::from_millis(300));
+			assert!(policy.backoff(64) <= Duration::from_millis(300));
+		}
+	}
+
+	#[test]
+	fn test_retryable_statuses() {
+		let policy = RetryPolicy::default();
+		let error = |status| LunoError::UnexpectedResponse {
+			status,
+			body: String::new(),
+		};
+		assert!(policy.is_retryable(&error(StatusCode::SERVICE_UNAVAILABLE)));
+		assert!(!policy.is_retryable(&error(StatusCode::BAD_REQUEST)));
+	}
+}
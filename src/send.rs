@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use reqwest::Url;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::{error::LunoError, Currency, LunoClient};
+
+/// Contains the result of sending cryptocurrency.
+#[derive(Debug, Deserialize)]
+pub struct SendResponse {
+	pub success: bool,
+	/// The ID of the withdrawal made to send the funds.
+	pub withdrawal_id: String,
+}
+
+/// Represents the fee charged for sending cryptocurrency.
+#[derive(Debug, Deserialize)]
+pub struct SendFee {
+	pub currency: Currency,
+	pub fee: Decimal,
+}
+
+/// Contains the result of validating a cryptocurrency address.
+#[derive(Debug, Deserialize)]
+pub struct AddressValidation {
+	pub success: bool,
+}
+
+/// A builder for the `send()` method.
+pub struct SendBuilder<'a> {
+	pub(crate) luno_client: &'a LunoClient,
+	pub(crate) url: Url,
+	pub(crate) params: HashMap<&'a str, String>,
+	pub(crate) validation: Option<ValidateAddressBuilder<'a>>,
+}
+
+impl<'a> SendBuilder<'a> {
+	/// Sets the destination tag (or memo) required by some addresses, e.g. XRP addresses
+	/// shared by an exchange.
+	pub fn with_destination_tag(&mut self, tag: u64) -> &mut SendBuilder<'a> {
+		self.params.insert("has_destination_tag", "true".to_owned());
+		self.params.insert("destination_tag", tag.to_string());
+		self
+	}
+
+	/// Sets the description shown in your transaction history.
+	pub fn with_description(&mut self, description: &str) -> &mut SendBuilder<'a> {
+		self.params.insert("description", description.to_owned());
+		self
+	}
+
+	/// Sets a unique ID for the send, which makes it safe to retry (see `RetryPolicy`).
+	pub fn with_external_id(&mut self, id: &str) -> &mut SendBuilder<'a> {
+		self.params.insert("external_id", id.to_owned());
+		self
+	}
+
+	/// Posts `validation`, as returned by `validate_address()` with any travel rule details set,
+	/// before sending, so that nothing is sent if Luno rejects the address. The destination tag
+	/// of the send is validated too unless `validation` sets one.
+	///
+	/// Sending fails with `LunoError::InvalidRequest` if `validation` is for a different address
+	/// or currency.
+	pub fn with_address_validation(
+		&mut self,
+		validation: ValidateAddressBuilder<'a>,
+	) -> &mut SendBuilder<'a> {
+		self.validation = Some(validation);
+		self
+	}
+
+	pub async fn post(&mut self) -> Result<SendResponse, LunoError> {
+		if let Some(validation) = &mut self.validation {
+			for &key in ["address", "currency"].iter() {
+				if validation.params.get(key) != self.params.get(key) {
+					return Err(LunoError::InvalidRequest(format!(
+						"address validation is for a different {} than the send",
+						key
+					)));
+				}
+			}
+			for &key in ["has_destination_tag", "destination_tag"].iter() {
+				if let Some(value) = self.params.get(key) {
+					validation
+						.params
+						.entry(key)
+						.or_insert_with(|| value.clone());
+				}
+			}
+			validation.post().await?;
+		}
+
+		let url = self.url.clone();
+		self.luno_client
+			.post_maybe_idempotent(url, &self.params, "external_id")
+			.await
+	}
+}
+
+/// A builder for the `validate_address()` method.
+pub struct ValidateAddressBuilder<'a> {
+	pub(crate) luno_client: &'a LunoClient,
+	pub(crate) url: Url,
+	pub(crate) params: HashMap<&'a str, String>,
+}
+
+impl<'a> ValidateAddressBuilder<'a> {
+	/// Sets the destination tag (or memo) that will be sent with the funds.
+	pub fn with_destination_tag(&mut self, tag: u64) -> &mut ValidateAddressBuilder<'a> {
+		self.params.insert("has_destination_tag", "true".to_owned());
+		self.params.insert("destination_tag", tag.to_string());
+		self
+	}
+
+	/// Declares that the address belongs to you, for the travel rule.
+	pub fn self_send(&mut self) -> &mut ValidateAddressBuilder<'a> {
+		self.params.insert("is_self_send", "true".to_owned());
+		self
+	}
+
+	/// Declares that the address belongs to a private wallet, such as a hardware wallet,
+	/// rather than an exchange, for the travel rule.
+	pub fn private_wallet(&mut self, wallet_name: &str) -> &mut ValidateAddressBuilder<'a> {
+		self.params.insert("is_private_wallet", "true".to_owned());
+		self.params
+			.insert("private_wallet_name", wallet_name.to_owned());
+		self
+	}
+
+	/// Sets the name of the exchange or institution that holds the address, for the travel rule.
+	pub fn with_institution(&mut self, name: &str) -> &mut ValidateAddressBuilder<'a> {
+		self.params.insert("institution_name", name.to_owned());
+		self
+	}
+
+	/// Sets the name of the person or, if `is_legal_entity` is set, the company that the address
+	/// belongs to, for the travel rule.
+	pub fn with_beneficiary(
+		&mut self,
+		name: &str,
+		is_legal_entity: bool,
+	) -> &mut ValidateAddressBuilder<'a> {
+		self.params.insert("beneficiary_name", name.to_owned());
+		self.params
+			.insert("is_legal_entity", is_legal_entity.to_string());
+		self
+	}
+
+	/// Validates the address, failing with `ErrorCode::ErrInvalidAddress` or another error
+	/// code describing the problem if Luno rejects it.
+	pub async fn post(&mut self) -> Result<AddressValidation, LunoError> {
+		let url = self.url.clone();
+		let validation: AddressValidation =
+			self.luno_client.post_idempotent(url, &self.params).await?;
+		if !validation.success {
+			return Err(LunoError::InvalidRequest(format!(
+				"address {} failed validation",
+				self.params["address"]
+			)));
+		}
+		Ok(validation)
+	}
+}
@@ -0,0 +1,504 @@
+//! Support for the Luno [Streaming API](https://www.luno.com/en/developers/api#tag/Streaming-API).
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::{stream, SinkExt, Stream, StreamExt};
+use reqwest::Url;
+use rust_decimal::Decimal;
+use serde::{de, Deserialize, Deserializer};
+use serde_json::json;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+use crate::{
+	error::LunoError, orders::OrderState, Ask, Bid, Credentials, LimitOrderType, Orderbook,
+	RetryPolicy, TradingPair,
+};
+
+/// The number of failed attempts in a row to reconnect a `UserStream` before it ends.
+const RECONNECT_ATTEMPTS: u32 = 5;
+
+pub(crate) type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Opens a websocket to `url` and authenticates with `credentials`.
+pub(crate) async fn connect(url: &Url, credentials: &Credentials) -> Result<Socket, LunoError> {
+	let (mut socket, _) = connect_async(url.as_str()).await?;
+	let auth = json!({
+		"api_key_id": credentials.key,
+		"api_key_secret": credentials.secret,
+	});
+	socket.send(Message::Text(auth.to_string())).await?;
+	Ok(socket)
+}
+
+/// Waits for the next message on `socket` that carries data, skipping keep-alive messages.
+///
+/// Returns `None` if the connection was closed.
+pub(crate) async fn next_message(socket: &mut Socket) -> Result<Option<String>, LunoError> {
+	while let Some(message) = socket.next().await {
+		match message? {
+			Message::Text(text) if is_keep_alive(&text) => continue,
+			Message::Text(text) => return Ok(Some(text)),
+			Message::Close(_) => return Ok(None),
+			_ => continue,
+		}
+	}
+	Ok(None)
+}
+
+fn is_keep_alive(text: &str) -> bool {
+	let text = text.trim();
+	text.is_empty() || text == "\"\""
+}
+
+/// Deserializes a sequence number, which the Streaming API sends as a string.
+fn deserialize_sequence<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum Sequence {
+		Number(u64),
+		Text(String),
+	}
+
+	match Sequence::deserialize(deserializer)? {
+		Sequence::Number(sequence) => Ok(sequence),
+		Sequence::Text(sequence) => sequence.parse().map_err(de::Error::custom),
+	}
+}
+
+/// Represents an order in the initial order book sent by the Streaming API.
+#[derive(Debug, Deserialize)]
+struct StreamOrder {
+	id: String,
+	price: Decimal,
+	volume: Decimal,
+}
+
+/// The initial state of the order book sent after connecting.
+#[derive(Debug, Deserialize)]
+struct Snapshot {
+	#[serde(deserialize_with = "deserialize_sequence")]
+	sequence: u64,
+	asks: Vec<StreamOrder>,
+	bids: Vec<StreamOrder>,
+	status: String,
+	timestamp: u64,
+}
+
+/// Represents a trade between two orders in the order book.
+#[derive(Debug, Deserialize)]
+pub struct TradeUpdate {
+	/// The base volume traded.
+	pub base: Decimal,
+	/// The counter volume traded.
+	pub counter: Decimal,
+	/// The ID of the order in the order book that was filled.
+	pub maker_order_id: String,
+	/// The ID of the order that filled the maker order.
+	pub taker_order_id: String,
+}
+
+/// Represents an order added to the order book.
+#[derive(Debug, Deserialize)]
+pub struct CreateUpdate {
+	pub order_id: String,
+	#[serde(rename = "type")]
+	pub order_type: LimitOrderType,
+	pub price: Decimal,
+	pub volume: Decimal,
+}
+
+/// Represents an order removed from the order book.
+#[derive(Debug, Deserialize)]
+pub struct DeleteUpdate {
+	pub order_id: String,
+}
+
+/// Represents a change to the status of the market.
+#[derive(Debug, Deserialize)]
+pub struct StatusUpdate {
+	pub status: String,
+}
+
+/// Represents an update to the order book sent by the Streaming API.
+#[derive(Debug, Deserialize)]
+pub struct OrderbookUpdate {
+	/// The sequence number of the update, which is one more than that of the previous update.
+	#[serde(deserialize_with = "deserialize_sequence")]
+	pub sequence: u64,
+	#[serde(default)]
+	pub trade_updates: Option<Vec<TradeUpdate>>,
+	#[serde(default)]
+	pub create_update: Option<CreateUpdate>,
+	#[serde(default)]
+	pub delete_update: Option<DeleteUpdate>,
+	#[serde(default)]
+	pub status_update: Option<StatusUpdate>,
+	pub timestamp: u64,
+}
+
+/// A live order book for a market, kept up to date by the Streaming API.
+///
+/// Call `next_update()` in a loop to receive updates, which are applied to the order book
+/// before they are returned. If an update is missed, the order book is reloaded by
+/// subscribing to the market again.
+pub struct OrderbookStream {
+	url: Url,
+	credentials: Credentials,
+	socket: Socket,
+	sequence: u64,
+	asks: HashMap<String, StreamOrder>,
+	bids: HashMap<String, StreamOrder>,
+	status: String,
+	timestamp: u64,
+}
+
+impl OrderbookStream {
+	pub(crate) async fn connect(
+		url: Url,
+		credentials: Credentials,
+	) -> Result<OrderbookStream, LunoError> {
+		let (socket, snapshot) = OrderbookStream::subscribe(&url, &credentials).await?;
+		let mut stream = OrderbookStream {
+			url,
+			credentials,
+			socket,
+			sequence: 0,
+			asks: HashMap::new(),
+			bids: HashMap::new(),
+			status: String::new(),
+			timestamp: 0,
+		};
+		stream.load(snapshot);
+		Ok(stream)
+	}
+
+	async fn subscribe(
+		url: &Url,
+		credentials: &Credentials,
+	) -> Result<(Socket, Snapshot), LunoError> {
+		let mut socket = connect(url, credentials).await?;
+		match next_message(&mut socket).await? {
+			Some(text) => Ok((socket, serde_json::from_str(&text)?)),
+			None => Err(LunoError::StreamClosed),
+		}
+	}
+
+	async fn resubscribe(&mut self) -> Result<(), LunoError> {
+		let (socket, snapshot) = OrderbookStream::subscribe(&self.url, &self.credentials).await?;
+		self.socket = socket;
+		self.load(snapshot);
+		Ok(())
+	}
+
+	fn load(&mut self, snapshot: Snapshot) {
+		self.sequence = snapshot.sequence;
+		self.asks = snapshot
+			.asks
+			.into_iter()
+			.map(|order| (order.id.clone(), order))
+			.collect();
+		self.bids = snapshot
+			.bids
+			.into_iter()
+			.map(|order| (order.id.clone(), order))
+			.collect();
+		self.status = snapshot.status;
+		self.timestamp = snapshot.timestamp;
+	}
+
+	/// Waits for the next update to the order book and applies it.
+	///
+	/// If the update does not follow the last one received, the order book is reloaded
+	/// and the next update after the reload is returned instead.
+	pub async fn next_update(&mut self) -> Result<OrderbookUpdate, LunoError> {
+		loop {
+			let text = match next_message(&mut self.socket).await? {
+				Some(text) => text,
+				None => {
+					self.resubscribe().await?;
+					continue;
+				}
+			};
+			let update: OrderbookUpdate = serde_json::from_str(&text)?;
+			if update.sequence <= self.sequence {
+				continue;
+			}
+			if update.sequence != self.sequence + 1 {
+				self.resubscribe().await?;
+				continue;
+			}
+			self.apply(&update);
+			return Ok(update);
+		}
+	}
+
+	fn apply(&mut self, update: &OrderbookUpdate) {
+		self.sequence = update.sequence;
+		self.timestamp = update.timestamp;
+
+		if let Some(trades) = &update.trade_updates {
+			for trade in trades {
+				for orders in [&mut self.asks, &mut self.bids] {
+					if let Some(order) = orders.get_mut(&trade.maker_order_id) {
+						order.volume -= trade.base;
+						if order.volume <= Decimal::ZERO {
+							orders.remove(&trade.maker_order_id);
+						}
+					}
+				}
+			}
+		}
+		if let Some(create) = &update.create_update {
+			let order = StreamOrder {
+				id: create.order_id.clone(),
+				price: create.price,
+				volume: create.volume,
+			};
+			match create.order_type {
+				LimitOrderType::ASK => self.asks.insert(order.id.clone(), order),
+				LimitOrderType::BID => self.bids.insert(order.id.clone(), order),
+			};
+		}
+		if let Some(delete) = &update.delete_update {
+			self.asks.remove(&delete.order_id);
+			self.bids.remove(&delete.order_id);
+		}
+		if let Some(status) = &update.status_update {
+			self.status = status.status.clone();
+		}
+	}
+
+	/// Returns the current order book. Ask orders are sorted by price ascending and
+	/// bid orders are sorted by price descending. Orders at the same price are not aggregated.
+	pub fn orderbook(&self) -> Orderbook {
+		let mut asks: Vec<Ask> = self
+			.asks
+			.values()
+			.map(|order| Ask {
+				price: order.price,
+				volume: order.volume,
+			})
+			.collect();
+		asks.sort_by_key(|ask| ask.price);
+		let mut bids: Vec<Bid> = self
+			.bids
+			.values()
+			.map(|order| Bid {
+				price: order.price,
+				volume: order.volume,
+			})
+			.collect();
+		bids.sort_by_key(|bid| Reverse(bid.price));
+
+		Orderbook {
+			asks,
+			bids,
+			timestamp: self.timestamp,
+		}
+	}
+
+	/// Returns the sequence number of the last update applied to the order book.
+	pub fn sequence(&self) -> u64 {
+		self.sequence
+	}
+
+	/// Returns the status of the market, e.g. `ACTIVE`.
+	pub fn status(&self) -> &str {
+		&self.status
+	}
+}
+
+/// Represents a change to the status of one of your orders.
+#[derive(Debug, Deserialize)]
+pub struct OrderStatusUpdate {
+	/// The UNIX timestamp of the event.
+	#[serde(default)]
+	pub timestamp: u64,
+	pub order_id: String,
+	#[serde(default)]
+	pub client_order_id: Option<String>,
+	/// The market trading pair.
+	pub market_id: TradingPair,
+	/// The new state of the order.
+	pub status: OrderState,
+}
+
+/// Represents a trade that filled one of your orders.
+#[derive(Debug, Deserialize)]
+pub struct OrderFillUpdate {
+	/// The UNIX timestamp of the event.
+	#[serde(default)]
+	pub timestamp: u64,
+	pub order_id: String,
+	#[serde(default)]
+	pub client_order_id: Option<String>,
+	/// The market trading pair.
+	pub market_id: TradingPair,
+	/// The total base volume filled so far.
+	pub base_fill: Decimal,
+	/// The total counter volume filled so far.
+	pub counter_fill: Decimal,
+	/// The base volume filled by this trade.
+	pub base_delta: Decimal,
+	/// The counter volume filled by this trade.
+	pub counter_delta: Decimal,
+	/// The total base fee charged so far.
+	pub base_fee: Decimal,
+	/// The total counter fee charged so far.
+	pub counter_fee: Decimal,
+	/// The base fee charged for this trade.
+	pub base_fee_delta: Decimal,
+	/// The counter fee charged for this trade.
+	pub counter_fee_delta: Decimal,
+}
+
+/// Represents a change to the balance of one of your accounts.
+#[derive(Debug, Deserialize)]
+pub struct BalanceUpdate {
+	/// The UNIX timestamp of the event.
+	#[serde(default)]
+	pub timestamp: u64,
+	pub account_id: String,
+	/// The row of the transaction that changed the balance.
+	pub row_index: u64,
+	pub balance: Decimal,
+	pub balance_delta: Decimal,
+	pub available: Decimal,
+	pub available_delta: Decimal,
+}
+
+/// Represents an event sent on the user stream.
+#[derive(Debug)]
+pub enum UserEvent {
+	OrderStatus(OrderStatusUpdate),
+	OrderFill(OrderFillUpdate),
+	BalanceUpdate(BalanceUpdate),
+}
+
+/// A message sent on the user stream.
+#[derive(Debug, Deserialize)]
+struct UserStreamMessage {
+	#[serde(rename = "type")]
+	message_type: String,
+	timestamp: u64,
+	order_status_update: Option<OrderStatusUpdate>,
+	order_fill_update: Option<OrderFillUpdate>,
+	balance_update: Option<BalanceUpdate>,
+}
+
+impl UserStreamMessage {
+	/// Returns the event carried by the message, if it is of a known type.
+	fn into_event(self) -> Option<UserEvent> {
+		let timestamp = self.timestamp;
+		match self.message_type.as_str() {
+			"order_status" => self.order_status_update.map(|mut update| {
+				update.timestamp = timestamp;
+				UserEvent::OrderStatus(update)
+			}),
+			"order_fill" => self.order_fill_update.map(|mut update| {
+				update.timestamp = timestamp;
+				UserEvent::OrderFill(update)
+			}),
+			"balance_update" => self.balance_update.map(|mut update| {
+				update.timestamp = timestamp;
+				UserEvent::BalanceUpdate(update)
+			}),
+			_ => None,
+		}
+	}
+}
+
+struct UserStreamState {
+	url: Url,
+	credentials: Credentials,
+	/// The open connection, or `None` if it was lost and must be re-established.
+	socket: Option<Socket>,
+	/// The number of failed attempts in a row to reconnect.
+	failures: u32,
+	reconnect_policy: RetryPolicy,
+}
+
+/// A stream of events about your orders and balances from the Streaming API.
+///
+/// If the connection is lost it is re-established automatically, after a delay that grows with
+/// each failed attempt. Events that occur while reconnecting are not received. Errors from the
+/// connection are returned by the stream as they occur, and the stream ends after five attempts
+/// in a row to reconnect fail.
+pub struct UserStream {
+	inner: Pin<Box<dyn Stream<Item = Result<UserEvent, LunoError>> + Send>>,
+}
+
+impl UserStream {
+	pub(crate) async fn connect(
+		url: Url,
+		credentials: Credentials,
+	) -> Result<UserStream, LunoError> {
+		let socket = connect(&url, &credentials).await?;
+		let state = UserStreamState {
+			url,
+			credentials,
+			socket: Some(socket),
+			failures: 0,
+			reconnect_policy: RetryPolicy {
+				max_attempts: RECONNECT_ATTEMPTS,
+				initial_backoff: Duration::from_millis(500),
+				max_backoff: Duration::from_secs(30),
+				..RetryPolicy::default()
+			},
+		};
+		let inner = stream::unfold(Some(state), |state| async move {
+			let mut state = state?;
+			loop {
+				let socket = match &mut state.socket {
+					Some(socket) => socket,
+					None => {
+						let delay = state.reconnect_policy.backoff(state.failures + 1);
+						tokio::time::sleep(delay).await;
+						match connect(&state.url, &state.credentials).await {
+							Ok(socket) => {
+								state.failures = 0;
+								state.socket.insert(socket)
+							}
+							Err(e) => {
+								state.failures += 1;
+								let retry = state.failures < state.reconnect_policy.max_attempts;
+								return Some((Err(e), retry.then_some(state)));
+							}
+						}
+					}
+				};
+				match next_message(socket).await {
+					Ok(Some(text)) => match serde_json::from_str::<UserStreamMessage>(&text) {
+						Ok(message) => match message.into_event() {
+							Some(event) => return Some((Ok(event), Some(state))),
+							None => continue,
+						},
+						Err(e) => return Some((Err(e.into()), Some(state))),
+					},
+					Ok(None) => state.socket = None,
+					Err(e) => {
+						state.socket = None;
+						return Some((Err(e), Some(state)));
+					}
+				}
+			}
+		});
+
+		Ok(UserStream {
+			inner: Box::pin(inner),
+		})
+	}
+}
+
+impl Stream for UserStream {
+	type Item = Result<UserEvent, LunoError>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.inner.as_mut().poll_next(cx)
+	}
+}
@@ -1,10 +1,14 @@
 use serde::Deserialize;
 
+use futures::{stream, Stream};
 use reqwest::Url;
 use rust_decimal::Decimal;
 
 use crate::{error::LunoError, LimitOrderType, LunoClient, TradingPair};
 
+/// The largest number of trades Luno returns in a single page.
+const TRADES_PAGE_SIZE: u64 = 1000;
+
 /// Represents a trade made on the exchange.
 #[derive(Debug, Deserialize)]
 pub struct OwnTrade {
@@ -19,9 +23,12 @@ pub struct OwnTrade {
 	#[serde(alias = "type")]
 	pub order_type: LimitOrderType,
 	pub volume: Decimal,
+	/// The sequence number of the trade, which increases with each trade you make.
+	pub sequence: u64,
 }
 
 /// A builder for the `list_trades()` method.
+#[derive(Clone)]
 pub struct ListOwnTradesBuilder<'a> {
 	pub(crate) limit: Option<u64>,
 	pub(crate) since: Option<u64>,
@@ -96,6 +103,42 @@ impl<'a> ListOwnTradesBuilder<'a> {
 			.await?
 			.trades)
 	}
+
+	/// Returns a stream of all the trades matching the query, fetching further pages as needed.
+	///
+	/// Pages are walked by sequence number, from oldest to newest unless `sort_desc(true)` or
+	/// `before()` is set, in which case Luno returns the newest trades first. The page size is set with `limit()`, and defaults to the largest page Luno allows.
+	/// The stream ends after the last page or the first error.
+	pub fn stream(&self) -> impl Stream<Item = Result<OwnTrade, LunoError>> + 'a {
+		let mut builder = self.clone();
+		let page_size = *builder.limit.get_or_insert(TRADES_PAGE_SIZE);
+		let descending = builder.before.is_some() || builder.sort_desc == Some(true);
+		let pages = (Some(builder), Vec::new().into_iter());
+
+		stream::unfold(pages, move |(mut next, mut trades)| async move {
+			loop {
+				if let Some(trade) = trades.next() {
+					return Some((Ok(trade), (next, trades)));
+				}
+
+				let mut builder = next.take()?;
+				let page = match builder.list().await {
+					Ok(page) => page,
+					Err(e) => return Some((Err(e), (None, trades))),
+				};
+				if let Some(last) = page.last().filter(|_| page.len() as u64 >= page_size) {
+					// `before_seq` is exclusive while `after_seq` is inclusive.
+					if descending {
+						builder.before_seq = Some(last.sequence);
+					} else {
+						builder.after_seq = Some(last.sequence + 1);
+					}
+					next = Some(builder);
+				}
+				trades = page.into_iter();
+			}
+		})
+	}
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,7 +1,13 @@
+use std::cmp::Reverse;
+
+use futures::{stream, Stream};
 use rust_decimal::Decimal;
 use serde::Deserialize;
 
-use crate::market::Currency;
+use crate::{error::LunoError, market::Currency, LunoClient};
+
+/// The largest number of rows Luno returns in a single page.
+const TRANSACTIONS_PAGE_SIZE: i64 = 1000;
 
 /// Represents a transaction on an account.
 #[derive(Debug, Deserialize)]
@@ -29,3 +35,115 @@ pub struct ListPendingTransactionsResponse {
 	pub id: String,
 	pub pending: Vec<Transaction>,
 }
+
+/// A builder for the `transactions()` method.
+#[derive(Clone)]
+pub struct TransactionsBuilder<'a> {
+	pub(crate) luno_client: &'a LunoClient,
+	pub(crate) account_id: String,
+	pub(crate) after_row: Option<u64>,
+	pub(crate) before_row: Option<u64>,
+	pub(crate) newest_first: bool,
+}
+
+impl<'a> TransactionsBuilder<'a> {
+	/// Only returns rows after the given row index, e.g. to resume from the last row seen.
+	pub fn after_row(&mut self, row_index: u64) -> &mut TransactionsBuilder<'a> {
+		self.after_row = Some(row_index);
+		self
+	}
+
+	/// Only returns rows before the given row index.
+	pub fn before_row(&mut self, row_index: u64) -> &mut TransactionsBuilder<'a> {
+		self.before_row = Some(row_index);
+		self
+	}
+
+	/// Returns the most recent rows first, rather than the oldest.
+	pub fn newest_first(&mut self) -> &mut TransactionsBuilder<'a> {
+		self.newest_first = true;
+		self
+	}
+
+	/// Returns a stream of all the rows matching the query, fetching further pages as needed.
+	///
+	/// The stream ends after the last page or the first error.
+	pub fn stream(&self) -> impl Stream<Item = Result<Transaction, LunoError>> + 'a {
+		let builder = self.clone();
+		let range = builder.first_range();
+		let pages = (builder, range, Vec::new().into_iter());
+
+		stream::unfold(pages, |(builder, mut range, mut transactions)| async move {
+			loop {
+				if let Some(transaction) = transactions.next() {
+					return Some((Ok(transaction), (builder, range, transactions)));
+				}
+
+				let (min_row, max_row) = range.take()?;
+				let mut page = match builder
+					.luno_client
+					.list_transactions(&builder.account_id, min_row, max_row)
+					.await
+				{
+					Ok(response) => response.transactions,
+					Err(e) => return Some((Err(e), (builder, None, transactions))),
+				};
+				range = builder.next_range((min_row, max_row), &page);
+
+				page.retain(|transaction| builder.contains(transaction.row_index));
+				if builder.newest_first {
+					page.sort_by_key(|transaction| Reverse(transaction.row_index));
+				} else {
+					page.sort_by_key(|transaction| transaction.row_index);
+				}
+				transactions = page.into_iter();
+			}
+		})
+	}
+
+	/// The lowest row index that may be requested. Rows are numbered from 1, and non-positive
+	/// row numbers wrap around the most recent row instead.
+	fn floor(&self) -> i64 {
+		self.after_row.map_or(1, |row| row as i64 + 1)
+	}
+
+	fn contains(&self, row_index: u64) -> bool {
+		!matches!(self.after_row, Some(row) if row_index <= row)
+			&& !matches!(self.before_row, Some(row) if row_index >= row)
+	}
+
+	/// Returns the range of rows of the first page, as `(min_row, max_row)`.
+	fn first_range(&self) -> Option<(i64, i64)> {
+		match (self.newest_first, self.before_row) {
+			(false, _) => self.oldest_first_range(self.floor()),
+			(true, Some(before_row)) => self.newest_first_range(before_row as i64),
+			// The latest row is unknown, so wrap around it to fetch the most recent rows.
+			(true, None) => Some((-TRANSACTIONS_PAGE_SIZE, 0)),
+		}
+	}
+
+	/// Returns the range of rows of the page after `page`, which was fetched for `range`.
+	fn next_range(&self, range: (i64, i64), page: &[Transaction]) -> Option<(i64, i64)> {
+		if self.newest_first {
+			let oldest = page.iter().map(|transaction| transaction.row_index).min()?;
+			self.newest_first_range(oldest as i64)
+		} else if (page.len() as i64) < range.1 - range.0 {
+			None
+		} else {
+			self.oldest_first_range(range.1)
+		}
+	}
+
+	fn oldest_first_range(&self, min_row: i64) -> Option<(i64, i64)> {
+		let mut max_row = min_row + TRANSACTIONS_PAGE_SIZE;
+		if let Some(before_row) = self.before_row {
+			max_row = max_row.min(before_row as i64);
+		}
+		Some((min_row, max_row)).filter(|_| min_row < max_row)
+	}
+
+	fn newest_first_range(&self, max_row: i64) -> Option<(i64, i64)> {
+		let min_row = (max_row - TRANSACTIONS_PAGE_SIZE).max(self.floor());
+		Some((min_row, max_row)).filter(|_| min_row < max_row)
+	}
+}
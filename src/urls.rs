@@ -1,16 +1,28 @@
 use reqwest::Url;
+use rust_decimal::Decimal;
 
-use crate::TradingPair;
+use crate::{Currency, TradingPair};
 
 pub struct UrlMaker {
 	api_base: Url,
+	stream_base: Url,
 }
 
 impl UrlMaker {
 	/// Convenience constructor for `UrlMaker`.
-	pub fn new(api_base: &str) -> UrlMaker {
-		let url = Url::parse(api_base).unwrap();
-		UrlMaker { api_base: url }
+	pub fn new(api_base: &str, stream_base: &str) -> UrlMaker {
+		UrlMaker {
+			api_base: Url::parse(api_base).unwrap(),
+			stream_base: Url::parse(stream_base).unwrap(),
+		}
+	}
+
+	/// Creates a `UrlMaker` for already parsed API and Streaming API roots.
+	pub fn from_urls(api_base: Url, stream_base: Url) -> UrlMaker {
+		UrlMaker {
+			api_base,
+			stream_base,
+		}
 	}
 
 	/// Append a path to the API root
@@ -18,6 +30,12 @@ impl UrlMaker {
 		self.api_base.join(path).unwrap()
 	}
 
+	/// Append a path to the root of a version of the exchange API,
+	/// which is a sibling of the API root.
+	fn build_exchange_url(&self, version: u8, path: &str) -> Url {
+		self.build_url(&format!("../exchange/{}/{}", version, path))
+	}
+
 	/// Build https://api.mybitx.com/api/1/ticker?pair=...
 	pub fn ticker(&self, pair: TradingPair) -> Url {
 		let mut url = self.build_url("ticker");
@@ -51,6 +69,28 @@ impl UrlMaker {
 		url
 	}
 
+	// Build https://api.mybitx.com/api/exchange/1/markets
+	pub fn markets(&self) -> Url {
+		self.build_exchange_url(1, "markets")
+	}
+
+	// Build https://api.mybitx.com/api/exchange/1/move
+	pub fn moves(&self) -> Url {
+		self.build_exchange_url(1, "move")
+	}
+
+	// Build https://api.mybitx.com/api/exchange/1/move/list_moves
+	pub fn list_moves(&self) -> Url {
+		self.build_exchange_url(1, "move/list_moves")
+	}
+
+	// Build https://api.mybitx.com/api/exchange/1/candles?pair=...
+	pub fn candles(&self, pair: TradingPair) -> Url {
+		let mut url = self.build_exchange_url(1, "candles");
+		url.query_pairs_mut().append_pair("pair", &pair.to_string());
+		url
+	}
+
 	// Build https://api.mybitx.com/api/1/accounts
 	pub fn accounts(&self) -> Url {
 		self.build_url("accounts")
@@ -95,6 +135,13 @@ impl UrlMaker {
 		self.build_url("beneficiaries")
 	}
 
+	// Build https://api.mybitx.com/api/1/beneficiaries/{id}
+	pub fn beneficiary(&self, id: &str) -> Url {
+		let mut url = self.beneficiaries();
+		url.path_segments_mut().unwrap().push(id);
+		url
+	}
+
 	// Build https://api.mybitx.com/api/1/listorders
 	pub fn list_orders(&self) -> Url {
 		self.build_url("listorders")
@@ -122,6 +169,11 @@ impl UrlMaker {
 		url
 	}
 
+	// Build https://api.mybitx.com/api/exchange/3/order
+	pub fn order_v3(&self) -> Url {
+		self.build_exchange_url(3, "order")
+	}
+
 	// Build https://api.mybitx.com/api/1/listtrades?pair=...
 	pub fn list_trades(&self, pair: TradingPair) -> Url {
 		let mut url = self.build_url("listtrades");
@@ -136,6 +188,60 @@ impl UrlMaker {
 		url
 	}
 
+	// Build https://api.mybitx.com/api/1/funding_address
+	pub fn funding_address(&self) -> Url {
+		self.build_url("funding_address")
+	}
+
+	// Build https://api.mybitx.com/api/1/send
+	pub fn send(&self) -> Url {
+		self.build_url("send")
+	}
+
+	// Build https://api.mybitx.com/api/1/address/validate
+	pub fn validate_address(&self) -> Url {
+		self.build_url("address/validate")
+	}
+
+	// Build https://api.mybitx.com/api/1/send_fee?amount=...&currency=...&address=...
+	pub fn send_fee(&self, amount: Decimal, currency: &Currency, address: &str) -> Url {
+		let mut url = self.build_url("send_fee");
+		url.query_pairs_mut()
+			.append_pair("amount", &amount.to_string())
+			.append_pair("currency", currency.as_str())
+			.append_pair("address", address);
+		url
+	}
+
+	// Build https://api.mybitx.com/api/1/lightning/receive
+	pub fn lightning_receive(&self) -> Url {
+		self.build_url("lightning/receive")
+	}
+
+	// Build https://api.mybitx.com/api/1/lightning/receive/{id}
+	pub fn lightning_invoice(&self, id: &str) -> Url {
+		let mut url = self.lightning_receive();
+		url.path_segments_mut().unwrap().push(id);
+		url
+	}
+
+	// Build https://api.mybitx.com/api/1/lightning/send
+	pub fn lightning_send(&self) -> Url {
+		self.build_url("lightning/send")
+	}
+
+	// Build https://api.mybitx.com/api/1/withdrawals
+	pub fn withdrawals(&self) -> Url {
+		self.build_url("withdrawals")
+	}
+
+	// Build https://api.mybitx.com/api/1/withdrawals/{id}
+	pub fn withdrawal(&self, id: &str) -> Url {
+		let mut url = self.withdrawals();
+		url.path_segments_mut().unwrap().push(id);
+		url
+	}
+
 	// Build https://api.mybitx.com/api/1/quotes
 	pub fn quotes(&self) -> Url {
 		self.build_url("quotes")
@@ -147,4 +253,16 @@ impl UrlMaker {
 		url.path_segments_mut().unwrap().extend(&[id]);
 		url
 	}
+
+	// Build wss://ws.luno.com/api/1/stream/{pair}
+	pub fn stream(&self, pair: TradingPair) -> Url {
+		let mut url = self.stream_base.join("stream").unwrap();
+		url.path_segments_mut().unwrap().extend(&[pair.to_string()]);
+		url
+	}
+
+	// Build wss://ws.luno.com/api/1/userstream
+	pub fn user_stream(&self) -> Url {
+		self.stream_base.join("userstream").unwrap()
+	}
 }
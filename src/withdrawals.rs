@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use reqwest::Url;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::{error::LunoError, Currency, LunoClient};
+
+string_enum! {
+	/// Represents the method used to make a fiat withdrawal.
+	pub enum WithdrawalType {
+		#[allow(non_camel_case_types)]
+		IDR_LLG,
+		#[allow(non_camel_case_types)]
+		KES_MPESA,
+		#[allow(non_camel_case_types)]
+		MYR_IBG,
+		#[allow(non_camel_case_types)]
+		NAD_EFT,
+		#[allow(non_camel_case_types)]
+		ZAR_EFT,
+	}
+}
+
+string_enum! {
+	/// Represents the status of a withdrawal.
+	pub enum WithdrawalStatus {
+		/// The withdrawal has been requested and can still be cancelled.
+		PENDING,
+		/// The withdrawal is being paid out.
+		PROCESSING,
+		COMPLETED,
+		CANCELLED,
+	}
+}
+
+/// Represents a withdrawal from one of your accounts.
+#[derive(Debug, Deserialize)]
+pub struct Withdrawal {
+	pub id: String,
+	pub status: WithdrawalStatus,
+	/// The UNIX timestamp at which the withdrawal was requested.
+	pub created_at: u64,
+	#[serde(rename = "type", default)]
+	pub withdrawal_type: Option<WithdrawalType>,
+	pub currency: Currency,
+	pub amount: Decimal,
+	pub fee: Decimal,
+	/// The external ID given when the withdrawal was requested, if any.
+	#[serde(default)]
+	pub external_id: Option<String>,
+}
+
+/// Contains a list of withdrawals.
+#[derive(Debug, Deserialize)]
+pub struct ListWithdrawalsResponse {
+	#[serde(default)]
+	pub withdrawals: Vec<Withdrawal>,
+}
+
+/// A builder for the `list_withdrawals()` method.
+pub struct ListWithdrawalsBuilder<'a> {
+	pub(crate) luno_client: &'a LunoClient,
+	pub(crate) url: Url,
+	pub(crate) limit: Option<u64>,
+	pub(crate) before_id: Option<String>,
+}
+
+impl<'a> ListWithdrawalsBuilder<'a> {
+	pub fn limit(&mut self, count: u64) -> &mut ListWithdrawalsBuilder<'a> {
+		self.limit = Some(count);
+		self
+	}
+
+	/// Only returns withdrawals requested before the withdrawal with the given ID.
+	pub fn before_id(&mut self, id: &str) -> &mut ListWithdrawalsBuilder<'a> {
+		self.before_id = Some(id.to_owned());
+		self
+	}
+
+	pub async fn list(&self) -> Result<Vec<Withdrawal>, LunoError> {
+		let mut url = self.url.clone();
+		if let Some(limit) = self.limit {
+			url.query_pairs_mut()
+				.append_pair("limit", &limit.to_string());
+		}
+		if let Some(id) = &self.before_id {
+			url.query_pairs_mut().append_pair("before_id", id);
+		}
+		Ok(self
+			.luno_client
+			.get::<ListWithdrawalsResponse>(url)
+			.await?
+			.withdrawals)
+	}
+}
+
+/// A builder for the `create_withdrawal()` method.
+pub struct CreateWithdrawalBuilder<'a> {
+	pub(crate) luno_client: &'a LunoClient,
+	pub(crate) url: Url,
+	pub(crate) params: HashMap<&'a str, String>,
+}
+
+impl<'a> CreateWithdrawalBuilder<'a> {
+	/// Sets the beneficiary to pay out to, as returned by `list_beneficiaries()`.
+	/// Defaults to your default beneficiary.
+	pub fn with_beneficiary(&mut self, id: &str) -> &mut CreateWithdrawalBuilder<'a> {
+		self.params.insert("beneficiary_id", id.to_owned());
+		self
+	}
+
+	/// Requests a fast withdrawal, if possible, which is charged a higher fee.
+	pub fn fast(&mut self) -> &mut CreateWithdrawalBuilder<'a> {
+		self.params.insert("fast", "true".to_owned());
+		self
+	}
+
+	/// Sets the reference shown on the beneficiary's bank statement.
+	pub fn with_reference(&mut self, reference: &str) -> &mut CreateWithdrawalBuilder<'a> {
+		self.params.insert("reference", reference.to_owned());
+		self
+	}
+
+	/// Sets a unique ID for the withdrawal, which makes it safe to retry (see `RetryPolicy`).
+	pub fn with_external_id(&mut self, id: &str) -> &mut CreateWithdrawalBuilder<'a> {
+		self.params.insert("external_id", id.to_owned());
+		self
+	}
+
+	pub async fn post(&mut self) -> Result<Withdrawal, LunoError> {
+		let url = self.url.clone();
+		self.luno_client
+			.post_maybe_idempotent(url, &self.params, "external_id")
+			.await
+	}
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use std::time::Duration;
+
+use mockito::{mock, Matcher};
+use rust_decimal_macros::dec;
+
+use luno::MoveStatus;
+
+use common::mock_client;
+
+fn move_body(id: &str, status: &str) -> String {
+	format!(
+		r#"{{"id":"{}","client_move_id":"rebalance-1","debit_account_id":12345,"credit_account_id":67890,"amount":"0.5","created_at":1557140000000,"updated_at":1557140001000,"status":"{}"}}"#,
+		id, status
+	)
+}
+
+#[tokio::test]
+async fn test_move_funds() {
+	let m = mock("POST", "/api/exchange/1/move")
+		.match_body(Matcher::AllOf(vec![
+			Matcher::UrlEncoded("debit_account_id".into(), "12345".into()),
+			Matcher::UrlEncoded("credit_account_id".into(), "67890".into()),
+			Matcher::UrlEncoded("amount".into(), "0.5".into()),
+			Matcher::UrlEncoded("client_move_id".into(), "rebalance-1".into()),
+		]))
+		.with_body(r#"{"id":"3001","status":"CREATED"}"#)
+		.expect(1)
+		.create();
+
+	let response = mock_client()
+		.move_funds("12345", "67890", dec!(0.5), Some("rebalance-1"))
+		.await
+		.unwrap();
+	assert_eq!(response.id, "3001");
+	assert_eq!(response.status, MoveStatus::CREATED);
+	m.assert();
+}
+
+#[tokio::test]
+async fn test_get_and_wait_for_move() {
+	let _m = mock("GET", "/api/exchange/1/move?id=3002")
+		.with_body(move_body("3002", "SUCCESSFUL"))
+		.create();
+
+	let client = mock_client();
+	let move_ = client.get_move("3002").await.unwrap();
+	assert_eq!(move_.debit_account_id, "12345");
+	assert_eq!(move_.client_move_id.as_deref(), Some("rebalance-1"));
+
+	let move_ = client
+		.wait_for_move("3002", Duration::from_secs(5))
+		.await
+		.unwrap();
+	assert_eq!(move_.status, MoveStatus::SUCCESSFUL);
+}
+
+#[tokio::test]
+async fn test_list_moves() {
+	let _m = mock(
+		"GET",
+		"/api/exchange/1/move/list_moves?before=1557140002000&limit=1",
+	)
+	.with_body(format!(r#"{{"moves":[{}]}}"#, move_body("3003", "MOVING")))
+	.create();
+
+	let moves = mock_client()
+		.list_moves()
+		.before(1557140002000)
+		.limit(1)
+		.list()
+		.await
+		.unwrap();
+	assert_eq!(moves.len(), 1);
+	assert_eq!(moves[0].status, MoveStatus::MOVING);
+	assert_eq!(moves[0].amount, dec!(0.5));
+}
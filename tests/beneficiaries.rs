@@ -0,0 +1,100 @@
+mod common;
+
+use mockito::{mock, Matcher};
+
+use luno::{error::LunoError, BankAccountType};
+
+use common::mock_client;
+
+#[tokio::test]
+async fn test_create_beneficiary() {
+	let m = mock("POST", "/api/1/beneficiaries")
+		.match_body(Matcher::AllOf(vec![
+			Matcher::UrlEncoded("account_type".into(), "Current/Cheque".into()),
+			Matcher::UrlEncoded("bank_name".into(), "Standard Bank".into()),
+			Matcher::UrlEncoded("bank_account_number".into(), "0123456789".into()),
+			Matcher::UrlEncoded("bank_recipient".into(), "Satoshi Nakamoto".into()),
+			Matcher::UrlEncoded("branch_code".into(), "051001".into()),
+		]))
+		.with_body(r#"{"bank_account_branch":"051001","bank_account_number":"0123456789","bank_account_type":"Current/Cheque","bank_country":"ZA","bank_name":"Standard Bank","bank_recipient":"Satoshi Nakamoto","created_at":1557140000000,"id":"2001"}"#)
+		.expect(1)
+		.create();
+
+	let beneficiary = mock_client()
+		.create_beneficiary(
+			BankAccountType::Current,
+			"Standard Bank",
+			" 0123456789 ",
+			"Satoshi Nakamoto",
+		)
+		.with_branch_code("051001")
+		.post()
+		.await
+		.unwrap();
+	assert_eq!(beneficiary.id, "2001");
+	m.assert();
+}
+
+#[tokio::test]
+async fn test_create_beneficiary_with_iban() {
+	let m = mock("POST", "/api/1/beneficiaries")
+		.match_body(Matcher::UrlEncoded(
+			"bank_account_number".into(),
+			"GB29NWBK60161331926819".into(),
+		))
+		.with_body(r#"{"bank_account_branch":"","bank_account_number":"GB29NWBK60161331926819","bank_account_type":"Savings","bank_country":"GB","bank_name":"NatWest","bank_recipient":"Satoshi Nakamoto","created_at":1557140000000,"id":"2002"}"#)
+		.expect(1)
+		.create();
+
+	let beneficiary = mock_client()
+		.create_beneficiary(
+			BankAccountType::Savings,
+			"NatWest",
+			"GB29NWBK60161331926819",
+			"Satoshi Nakamoto",
+		)
+		.post()
+		.await
+		.unwrap();
+	assert_eq!(beneficiary.id, "2002");
+	m.assert();
+}
+
+#[tokio::test]
+async fn test_create_beneficiary_validation() {
+	let client = mock_client();
+	let missing_name = client
+		.create_beneficiary(BankAccountType::Savings, "", "0123456789", "Satoshi")
+		.post()
+		.await;
+	assert!(matches!(missing_name, Err(LunoError::InvalidRequest(_))));
+
+	let missing_account = client
+		.create_beneficiary(BankAccountType::Savings, "Standard Bank", " ", "Satoshi")
+		.post()
+		.await;
+	assert!(matches!(missing_account, Err(LunoError::InvalidRequest(_))));
+
+	let bad_branch = client
+		.create_beneficiary(
+			BankAccountType::Savings,
+			"Standard Bank",
+			"0123456789",
+			"Satoshi",
+		)
+		.with_branch_code("05-10")
+		.post()
+		.await;
+	assert!(matches!(bad_branch, Err(LunoError::InvalidRequest(_))));
+}
+
+#[tokio::test]
+async fn test_delete_beneficiary() {
+	let m = mock("DELETE", "/api/1/beneficiaries/2002")
+		.with_body("{}")
+		.expect(1)
+		.create();
+
+	mock_client().delete_beneficiary("2002").await.unwrap();
+	m.assert();
+}
@@ -1 +1,109 @@
+use std::time::Duration;
 
+use mockito::mock;
+use reqwest::{
+	header::{HeaderMap, HeaderValue},
+	StatusCode,
+};
+
+use luno::{
+	error::{ErrorCode, LunoError},
+	LunoClient, TradingPair,
+};
+
+fn mock_client() -> LunoClient {
+	LunoClient::builder("TEST_KEY", "TEST_SECRET")
+		.with_base_url(&format!("{}/api/1", mockito::server_url()))
+		.build()
+		.unwrap()
+}
+
+#[tokio::test]
+async fn test_builder_base_url() {
+	let _m = mock("GET", "/api/1/ticker?pair=XBTZAR")
+		.with_body(r#"{"ask":"2.00","bid":"1.00","last_trade":"1.50","pair":"XBTZAR","rolling_24_hour_volume":"10.0","timestamp":1}"#)
+		.create();
+
+	let ticker = mock_client().get_ticker(TradingPair::XBTZAR).await.unwrap();
+	assert_eq!(ticker.timestamp, 1);
+}
+
+#[tokio::test]
+async fn test_builder_http_options() {
+	let _m = mock("GET", "/api/1/tickers")
+		.match_header("user-agent", "luno-tests")
+		.match_header("x-test", "yes")
+		.with_body(r#"{"tickers":[]}"#)
+		.create();
+
+	let mut headers = HeaderMap::new();
+	headers.insert("x-test", HeaderValue::from_static("yes"));
+	let client = LunoClient::builder("TEST_KEY", "TEST_SECRET")
+		.with_base_url(&format!("{}/api/1/", mockito::server_url()))
+		.with_timeout(Duration::from_secs(5))
+		.with_user_agent("luno-tests")
+		.with_default_headers(headers)
+		.build()
+		.unwrap();
+
+	assert!(client.list_tickers().await.unwrap().is_empty());
+}
+
+#[test]
+fn test_builder_invalid_config() {
+	assert!(matches!(
+		LunoClient::builder("TEST_KEY", "TEST_SECRET")
+			.with_base_url("not a url")
+			.build(),
+		Err(LunoError::InvalidUrl(_))
+	));
+	assert!(matches!(
+		LunoClient::builder("TEST_KEY", "TEST_SECRET")
+			.with_base_url("mailto:api@luno.com")
+			.build(),
+		Err(LunoError::InvalidRequest(_))
+	));
+	assert!(matches!(
+		LunoClient::builder("TEST_KEY", "TEST_SECRET")
+			.with_stream_url("data:text/plain,stream")
+			.build(),
+		Err(LunoError::InvalidRequest(_))
+	));
+	assert!(matches!(
+		LunoClient::builder("TEST_KEY", "TEST_SECRET")
+			.with_base_url("ftp://api.luno.com/api/1/")
+			.build(),
+		Err(LunoError::InvalidRequest(_))
+	));
+	assert!(matches!(
+		LunoClient::builder("TEST_KEY", "TEST_SECRET")
+			.with_http_client(reqwest::Client::new())
+			.with_timeout(Duration::from_secs(5))
+			.build(),
+		Err(LunoError::InvalidConfig(_))
+	));
+}
+
+#[tokio::test]
+async fn test_api_error() {
+	let _m = mock("GET", "/api/1/orders/BXMC2CJ7HNB88U4")
+		.with_status(404)
+		.with_body(
+			r#"{"error":"Order not found","error_code":"ErrOrderNotFound","error_action":{}}"#,
+		)
+		.create();
+
+	match mock_client().get_order("BXMC2CJ7HNB88U4").await {
+		Err(LunoError::ApiError {
+			status,
+			code,
+			message,
+			..
+		}) => {
+			assert_eq!(status, StatusCode::NOT_FOUND);
+			assert_eq!(code, ErrorCode::ErrOrderNotFound);
+			assert_eq!(message, "Order not found");
+		}
+		result => panic!("unexpected result: {:?}", result),
+	}
+}
@@ -0,0 +1,39 @@
+//! Helpers shared by the integration tests.
+// Not every test uses every helper.
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+
+use luno::LunoClient;
+
+/// Returns a client that sends its requests to the mock server.
+pub fn mock_client() -> LunoClient {
+	LunoClient::builder("TEST_KEY", "TEST_SECRET")
+		.with_base_url(&format!("{}/api/1/", mockito::server_url()))
+		.build()
+		.unwrap()
+}
+
+/// Builds an order as returned by `get_order()` or `list_orders()`, with `fields` set over
+/// defaults for the fields a test doesn't check.
+pub fn order_json(id: &str, state: &str, fields: Value) -> String {
+	let mut order = json!({
+		"order_id": id,
+		"pair": "XBTZAR",
+		"state": state,
+		"type": "BID",
+		"creation_timestamp": 1_367_849_297_609u64,
+		"expiration_timestamp": 0,
+		"completed_timestamp": 0,
+		"base": "0.0",
+		"counter": "0.0",
+		"fee_base": "0.0",
+		"fee_counter": "0.0",
+		"limit_price": "100000",
+		"limit_volume": "0.02",
+	});
+	if let (Some(order), Value::Object(fields)) = (order.as_object_mut(), fields) {
+		order.extend(fields);
+	}
+	order.to_string()
+}
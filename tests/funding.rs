@@ -0,0 +1,51 @@
+mod common;
+
+use mockito::{mock, Matcher};
+use rust_decimal_macros::dec;
+
+use luno::Currency;
+
+use common::mock_client;
+
+#[tokio::test]
+async fn test_get_funding_address() {
+	let _m = mock(
+		"GET",
+		"/api/1/funding_address?asset=XBT&address=B1tC0InExAMPL3fundIN6AdDreS5t0Use",
+	)
+	.with_body(r#"{"asset":"XBT","address":"B1tC0InExAMPL3fundIN6AdDreS5t0Use","name":"Deposits","account_id":"12345","assigned_at":1557140000000,"total_received":"1.234567","total_unconfirmed":"0.1","qr_code_uri":"bitcoin:B1tC0InExAMPL3fundIN6AdDreS5t0Use"}"#)
+	.create();
+
+	let address = mock_client()
+		.get_funding_address(Currency::XBT, Some("B1tC0InExAMPL3fundIN6AdDreS5t0Use"))
+		.await
+		.unwrap();
+	assert_eq!(address.asset, Currency::XBT);
+	assert_eq!(address.name.as_deref(), Some("Deposits"));
+	assert_eq!(address.total_received, dec!(1.234567));
+	assert_eq!(address.total_unconfirmed, dec!(0.1));
+}
+
+#[tokio::test]
+async fn test_create_funding_address() {
+	let m = mock("POST", "/api/1/funding_address")
+		.match_body(Matcher::AllOf(vec![
+			Matcher::UrlEncoded("asset".into(), "ETH".into()),
+			Matcher::UrlEncoded("name".into(), "Payroll".into()),
+			Matcher::UrlEncoded("account_id".into(), "67890".into()),
+		]))
+		.with_body(r#"{"asset":"ETH","address":"0xb794f5ea0ba39494ce839613fffba74279579268","name":"Payroll","account_id":"67890","total_received":"0","total_unconfirmed":"0"}"#)
+		.expect(1)
+		.create();
+
+	let address = mock_client()
+		.create_funding_address(Currency::ETH, Some("Payroll"), Some("67890"))
+		.await
+		.unwrap();
+	assert_eq!(
+		address.address,
+		"0xb794f5ea0ba39494ce839613fffba74279579268"
+	);
+	assert_eq!(address.account_id.as_deref(), Some("67890"));
+	m.assert();
+}
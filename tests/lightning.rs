@@ -0,0 +1,66 @@
+mod common;
+
+use mockito::{mock, Matcher};
+use rust_decimal_macros::dec;
+
+use luno::LightningStatus;
+
+use common::mock_client;
+
+#[tokio::test]
+async fn test_lightning_receive() {
+	let m = mock("POST", "/api/1/lightning/receive")
+		.match_body(Matcher::AllOf(vec![
+			Matcher::UrlEncoded("currency".into(), "XBT".into()),
+			Matcher::UrlEncoded("amount".into(), "0.0001".into()),
+			Matcher::UrlEncoded("description".into(), "Coffee".into()),
+		]))
+		.with_body(r#"{"invoice_id":4001,"payment_request":"lnbc10u1pexample"}"#)
+		.expect(1)
+		.create();
+
+	let invoice = mock_client()
+		.lightning_receive(dec!(0.0001))
+		.with_description("Coffee")
+		.post()
+		.await
+		.unwrap();
+	assert_eq!(invoice.invoice_id, "4001");
+	assert_eq!(invoice.payment_request, "lnbc10u1pexample");
+	assert!(invoice.status.is_none());
+	m.assert();
+}
+
+#[tokio::test]
+async fn test_get_lightning_invoice() {
+	let _m = mock("GET", "/api/1/lightning/receive/4002")
+		.with_body(r#"{"invoice_id":"4002","payment_request":"lnbc10u1pexample","amount":"0.0001","settled_amount":"0.0001","status":"SETTLED","created_at":1557140000000,"expires_at":1557143600000}"#)
+		.create();
+
+	let invoice = mock_client().get_lightning_invoice("4002").await.unwrap();
+	assert_eq!(invoice.status, Some(LightningStatus::SETTLED));
+	assert_eq!(invoice.settled_amount, Some(dec!(0.0001)));
+}
+
+#[tokio::test]
+async fn test_lightning_send() {
+	let m = mock("POST", "/api/1/lightning/send")
+		.match_body(Matcher::AllOf(vec![
+			Matcher::UrlEncoded("currency".into(), "XBT".into()),
+			Matcher::UrlEncoded("payment_request".into(), "lnbc20u1pexample".into()),
+			Matcher::UrlEncoded("external_id".into(), "payout-2".into()),
+		]))
+		.with_body(r#"{"invoice_id":4003,"status":"PENDING"}"#)
+		.expect(1)
+		.create();
+
+	let payment = mock_client()
+		.lightning_send("lnbc20u1pexample")
+		.with_external_id("payout-2")
+		.post()
+		.await
+		.unwrap();
+	assert_eq!(payment.invoice_id, "4003");
+	assert_eq!(payment.status, Some(LightningStatus::PENDING));
+	m.assert();
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use mockito::mock;
+use rust_decimal_macros::dec;
+
+use luno::{error::LunoError, TradingPair, TradingStatus};
+
+use common::mock_client;
+
+#[tokio::test]
+async fn test_list_markets() {
+	let _m = mock("GET", "/api/exchange/1/markets")
+		.with_body(r#"{"markets":[{"market_id":"XBTZAR","trading_status":"ACTIVE","base_currency":"XBT","counter_currency":"ZAR","min_volume":"0.0005","max_volume":"100.0","volume_scale":4,"min_price":"100","max_price":"10000000","price_scale":0,"fee_scale":8}]}"#)
+		.create();
+
+	let markets = mock_client().list_markets().await.unwrap();
+	assert_eq!(markets.len(), 1);
+	assert_eq!(markets[0].market_id, TradingPair::XBTZAR);
+	assert_eq!(markets[0].trading_status, TradingStatus::ACTIVE);
+	assert_eq!(markets[0].min_volume, dec!(0.0005));
+}
+
+#[tokio::test]
+async fn test_candles_pagination() {
+	let page = |start: u64| {
+		let candles: Vec<String> = (0..1000)
+			.map(|i| {
+				format!(
+					r#"{{"timestamp":{},"open":"1","high":"2","low":"0.5","close":"1.5","volume":"10"}}"#,
+					start + i * 60_000
+				)
+			})
+			.collect();
+		format!(
+			r#"{{"candles":[{}],"duration":60,"pair":"XBTZAR"}}"#,
+			candles.join(",")
+		)
+	};
+	let _first = mock(
+		"GET",
+		"/api/exchange/1/candles?pair=XBTZAR&since=0&duration=60",
+	)
+	.with_body(page(0))
+	.create();
+	let _second = mock(
+		"GET",
+		"/api/exchange/1/candles?pair=XBTZAR&since=60000000&duration=60",
+	)
+	.with_body(page(60_000_000))
+	.create();
+
+	let candles = mock_client()
+		.candles(TradingPair::XBTZAR, 0, 60)
+		.until(90_000_000)
+		.list()
+		.await
+		.unwrap();
+	assert_eq!(candles.len(), 1500);
+	assert_eq!(candles.last().unwrap().timestamp, 89_940_000);
+}
+
+#[tokio::test]
+async fn test_candles_invalid_duration() {
+	assert!(matches!(
+		mock_client()
+			.candles(TradingPair::XBTZAR, 0, 120)
+			.list()
+			.await,
+		Err(LunoError::InvalidRequest(_))
+	));
+}
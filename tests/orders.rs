@@ -0,0 +1,458 @@
+mod common;
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use mockito::{mock, Matcher};
+use rust_decimal_macros::dec;
+use serde_json::json;
+
+use luno::{
+	error::LunoError, orders::OrderState, LimitOrderType, LunoClient, MarketOrderType, OrderType,
+	TimeInForce, TradingPair,
+};
+
+use common::{mock_client, order_json};
+
+#[tokio::test]
+async fn test_get_order_by_client_id() {
+	let _m = mock("GET", "/api/exchange/3/order?client_order_id=lookup-1")
+		.with_body(r#"{"order_id":"BXMC2CJ7HNB88U4","client_order_id":"lookup-1","pair":"XBTZAR","status":"AWAITING","type":"LIMIT","side":"BUY","time_in_force":"GTC","creation_timestamp":1367849297609,"expiration_timestamp":0,"completed_timestamp":0,"base":"0.0","counter":"0.0","fee_base":"0.0","fee_counter":"0.0","limit_price":"100000","limit_volume":"0.01"}"#)
+		.create();
+
+	let order = mock_client()
+		.get_order_by_client_id("lookup-1")
+		.await
+		.unwrap();
+	assert_eq!(order.client_order_id.as_deref(), Some("lookup-1"));
+	assert_eq!(order.state, OrderState::AWAITING);
+	assert_eq!(order.order_type, OrderType::LIMIT);
+	assert_eq!(order.side, Some(MarketOrderType::BUY));
+	assert_eq!(order.time_in_force, Some(TimeInForce::GTC));
+}
+
+#[tokio::test]
+async fn test_post_or_recover_finds_placed_order() {
+	let post = mock("POST", "/api/1/postorder")
+		.match_body(Matcher::UrlEncoded(
+			"client_order_id".into(),
+			"recover-1".into(),
+		))
+		.with_status(502)
+		.expect(1)
+		.create();
+	let lookup = mock("GET", "/api/exchange/3/order?client_order_id=recover-1")
+		.with_body(r#"{"order_id":"BXMC2CJ7HNB88U4","client_order_id":"recover-1","pair":"XBTZAR","status":"AWAITING","type":"LIMIT","side":"BUY","creation_timestamp":1367849297609,"expiration_timestamp":0,"completed_timestamp":0,"base":"0.0","counter":"0.0","fee_base":"0.0","fee_counter":"0.0","limit_price":"100000","limit_volume":"0.01"}"#)
+		.expect(1)
+		.create();
+
+	let client = mock_client();
+	let response = client
+		.limit_order(
+			TradingPair::XBTZAR,
+			LimitOrderType::BID,
+			dec!(0.01),
+			dec!(100_000),
+		)
+		.with_client_order_id("recover-1")
+		.post_or_recover()
+		.await
+		.unwrap();
+	assert_eq!(response.order_id.as_deref(), Some("BXMC2CJ7HNB88U4"));
+	post.assert();
+	lookup.assert();
+}
+
+#[test]
+fn test_generated_client_order_id() {
+	let client = mock_client();
+	let mut builder = client.market_order(TradingPair::XBTZAR, MarketOrderType::BUY, dec!(100));
+	assert!(builder.client_order_id().is_none());
+	let id = builder
+		.with_generated_client_order_id()
+		.client_order_id()
+		.unwrap()
+		.to_owned();
+	assert_eq!(id.len(), 32);
+}
+
+#[tokio::test]
+async fn test_post_only_rejects_immediate_time_in_force() {
+	let client = mock_client();
+	let result = client
+		.limit_order(
+			TradingPair::XBTZAR,
+			LimitOrderType::BID,
+			dec!(0.01),
+			dec!(100_000),
+		)
+		.post_only()
+		.with_time_in_force(TimeInForce::IOC)
+		.post()
+		.await;
+	assert!(matches!(result, Err(LunoError::InvalidRequest(_))));
+}
+
+#[tokio::test]
+async fn test_cancel_all_orders() {
+	let _list = mock(
+		"GET",
+		"/api/1/listorders?state=PENDING&pair=ETHZAR&limit=1000",
+	)
+	.with_body(format!(
+		r#"{{"orders":[{},{}]}}"#,
+		order_json("CANCEL-OK", "PENDING", json!({})),
+		order_json("CANCEL-GONE", "PENDING", json!({}))
+	))
+	.create();
+	let _awaiting = mock(
+		"GET",
+		"/api/1/listorders?state=AWAITING&pair=ETHZAR&limit=1000",
+	)
+	.with_body(format!(
+		r#"{{"orders":[{}]}}"#,
+		order_json("CANCEL-STOP", "AWAITING", json!({ "stop_price": "90000" }))
+	))
+	.create();
+	let stop = mock("POST", "/api/1/stoporder")
+		.match_body("order_id=CANCEL-STOP")
+		.with_body(r#"{"success":true}"#)
+		.expect(1)
+		.create();
+	let ok = mock("POST", "/api/1/stoporder")
+		.match_body("order_id=CANCEL-OK")
+		.with_body(r#"{"success":true}"#)
+		.expect(1)
+		.create();
+	let gone = mock("POST", "/api/1/stoporder")
+		.match_body("order_id=CANCEL-GONE")
+		.with_status(404)
+		.with_body(r#"{"error":"Order not found","error_code":"ErrOrderNotFound"}"#)
+		.expect(1)
+		.create();
+
+	let mut cancelled = mock_client()
+		.cancel_all_orders(Some(TradingPair::ETHZAR))
+		.await
+		.unwrap();
+	cancelled.sort_by(|a, b| a.order_id.cmp(&b.order_id));
+	assert_eq!(cancelled.len(), 3);
+	assert!(matches!(
+		cancelled[0].result,
+		Err(LunoError::ApiError { .. })
+	));
+	assert!(cancelled[1].result.as_ref().unwrap().success);
+	assert_eq!(cancelled[2].order_id, "CANCEL-STOP");
+	assert!(cancelled[2].result.as_ref().unwrap().success);
+	stop.assert();
+	ok.assert();
+	gone.assert();
+}
+
+#[tokio::test]
+async fn test_cancel_all_orders_across_pages() {
+	let order = |id: &str, created: u64| {
+		order_json(id, "PENDING", json!({ "creation_timestamp": created }))
+	};
+	let first: Vec<String> = (0..1000)
+		.map(|i| order(&format!("PAGE-{}", i), 10_000 - i))
+		.collect();
+	let _awaiting = mock(
+		"GET",
+		"/api/1/listorders?state=AWAITING&pair=XBTEUR&limit=1000",
+	)
+	.with_body(r#"{"orders":[]}"#)
+	.expect(1)
+	.create();
+	let _first = mock(
+		"GET",
+		"/api/1/listorders?state=PENDING&pair=XBTEUR&limit=1000",
+	)
+	.with_body(format!(r#"{{"orders":[{}]}}"#, first.join(",")))
+	.expect(1)
+	.create();
+	let _second = mock(
+		"GET",
+		"/api/1/listorders?state=PENDING&pair=XBTEUR&created_before=9002&limit=1000",
+	)
+	.with_body(format!(
+		r#"{{"orders":[{},{}]}}"#,
+		order("PAGE-999", 9_001),
+		order("PAGE-LAST", 9_000)
+	))
+	.expect(1)
+	.create();
+	let cancel = mock("POST", "/api/1/stoporder")
+		.match_body(Matcher::Regex("^order_id=PAGE-".to_owned()))
+		.with_body(r#"{"success":true}"#)
+		.expect(1001)
+		.create();
+
+	let client = LunoClient::builder("TEST_KEY", "TEST_SECRET")
+		.with_base_url(&format!("{}/api/1/", mockito::server_url()))
+		.without_rate_limit()
+		.build()
+		.unwrap();
+	let cancelled = client
+		.cancel_all_orders(Some(TradingPair::XBTEUR))
+		.await
+		.unwrap();
+	assert_eq!(cancelled.len(), 1001);
+	assert!(cancelled.iter().any(|order| order.order_id == "PAGE-LAST"));
+	assert!(cancelled.iter().all(|order| order.result.is_ok()));
+	cancel.assert();
+}
+
+#[tokio::test]
+async fn test_order_handle_waits_for_fills() {
+	let _pending = mock("GET", "/api/1/orders/TRACK-1")
+		.with_body(order_json("TRACK-1", "PENDING", json!({})))
+		.expect(1)
+		.create();
+	let _partial = mock("GET", "/api/1/orders/TRACK-1")
+		.with_body(order_json("TRACK-1", "PENDING", json!({ "base": "0.01" })))
+		.expect(1)
+		.create();
+	let _complete = mock("GET", "/api/1/orders/TRACK-1")
+		.with_body(order_json("TRACK-1", "COMPLETE", json!({ "base": "0.02" })))
+		.create();
+
+	let client = mock_client();
+	let mut handle = client.track_order("TRACK-1");
+	handle.with_poll_interval(Duration::from_millis(1));
+	assert_eq!(handle.next_update().await.unwrap().base, dec!(0.0));
+	assert_eq!(handle.next_update().await.unwrap().base, dec!(0.01));
+
+	let order = handle.wait(Duration::from_secs(5)).await.unwrap();
+	assert_eq!(order.state, OrderState::COMPLETE);
+	assert_eq!(order.base, dec!(0.02));
+}
+
+#[tokio::test]
+async fn test_order_handle_returns_complete_order_straight_away() {
+	let get = mock("GET", "/api/1/orders/TRACK-3")
+		.with_body(order_json("TRACK-3", "COMPLETE", json!({ "base": "0.02" })))
+		.expect(1)
+		.create();
+	let cancel = mock("POST", "/api/1/stoporder")
+		.match_body("order_id=TRACK-3")
+		.expect(0)
+		.create();
+
+	let client = mock_client();
+	let mut handle = client.track_order("TRACK-3");
+	handle.with_poll_interval(Duration::from_secs(60));
+	assert_eq!(
+		handle.next_update().await.unwrap().state,
+		OrderState::COMPLETE
+	);
+
+	let order = handle.wait(Duration::from_secs(5)).await.unwrap();
+	assert_eq!(order.state, OrderState::COMPLETE);
+	let order = handle
+		.wait_or_cancel(Duration::from_millis(50))
+		.await
+		.unwrap();
+	assert_eq!(order.base, dec!(0.02));
+	get.assert();
+	cancel.assert();
+}
+
+#[tokio::test]
+async fn test_order_handle_cancels_on_timeout() {
+	let _get = mock("GET", "/api/1/orders/TRACK-2")
+		.with_body(order_json("TRACK-2", "PENDING", json!({})))
+		.create();
+	let cancel = mock("POST", "/api/1/stoporder")
+		.match_body("order_id=TRACK-2")
+		.with_body(r#"{"success":true}"#)
+		.expect(1)
+		.create();
+
+	let client = mock_client();
+	let mut handle = client.track_order("TRACK-2");
+	handle.with_poll_interval(Duration::from_millis(5));
+	assert!(matches!(
+		handle.wait(Duration::from_millis(50)).await,
+		Err(LunoError::Timeout)
+	));
+	let order = handle
+		.wait_or_cancel(Duration::from_millis(50))
+		.await
+		.unwrap();
+	assert_eq!(order.order_id, "TRACK-2");
+	cancel.assert();
+}
+
+#[tokio::test]
+async fn test_stream_orders_across_created_before() {
+	let order = |id: &str, created: u64| {
+		order_json(id, "COMPLETE", json!({ "creation_timestamp": created }))
+	};
+	let path = |created_before: Option<u64>| {
+		let mut path = "/api/1/listorders?state=COMPLETE&pair=LTCXBT".to_owned();
+		if let Some(created_before) = created_before {
+			path.push_str(&format!("&created_before={}", created_before));
+		}
+		path + "&limit=2"
+	};
+	let pages = vec![
+		mock("GET", path(None).as_str())
+			.with_body(format!(
+				r#"{{"orders":[{},{}]}}"#,
+				order("A", 300),
+				order("B", 200)
+			))
+			.expect(1)
+			.create(),
+		mock("GET", path(Some(201)).as_str())
+			.with_body(format!(
+				r#"{{"orders":[{},{}]}}"#,
+				order("B", 200),
+				order("C", 100)
+			))
+			.expect(1)
+			.create(),
+		mock("GET", path(Some(101)).as_str())
+			.with_body(format!(
+				r#"{{"orders":[{},{}]}}"#,
+				order("C", 100),
+				order("D", 50)
+			))
+			.expect(1)
+			.create(),
+		mock("GET", path(Some(51)).as_str())
+			.with_body(format!(r#"{{"orders":[{}]}}"#, order("D", 50)))
+			.expect(1)
+			.create(),
+	];
+
+	let client = mock_client();
+	let ids: Vec<String> = client
+		.orders()
+		.filter_state(OrderState::COMPLETE)
+		.filter_pair(TradingPair::LTCXBT)
+		.filter_limit(2)
+		.stream()
+		.map(|order| order.unwrap().order_id)
+		.collect()
+		.await;
+	assert_eq!(ids, vec!["A", "B", "C", "D"]);
+	for page in pages {
+		page.assert();
+	}
+}
+
+#[tokio::test]
+async fn test_stream_orders_fails_on_full_page_at_one_timestamp() {
+	let order = |id: &str, created: u64| {
+		order_json(id, "COMPLETE", json!({ "creation_timestamp": created }))
+	};
+	let _first = mock("GET", "/api/1/listorders?pair=ETHXBT&limit=2")
+		.with_body(format!(
+			r#"{{"orders":[{},{}]}}"#,
+			order("E", 300),
+			order("F", 200)
+		))
+		.expect(1)
+		.create();
+	let _second = mock(
+		"GET",
+		"/api/1/listorders?pair=ETHXBT&created_before=201&limit=2",
+	)
+	.with_body(format!(
+		r#"{{"orders":[{},{}]}}"#,
+		order("F", 200),
+		order("G", 200)
+	))
+	.expect(1)
+	.create();
+
+	let client = mock_client();
+	let orders: Vec<Result<_, _>> = client
+		.orders()
+		.filter_pair(TradingPair::ETHXBT)
+		.filter_limit(2)
+		.stream()
+		.collect()
+		.await;
+	assert_eq!(orders.len(), 4);
+	let ids: Vec<&str> = orders[..3]
+		.iter()
+		.map(|order| order.as_ref().unwrap().order_id.as_str())
+		.collect();
+	assert_eq!(ids, vec!["E", "F", "G"]);
+	assert!(matches!(
+		orders[3],
+		Err(LunoError::PageFull { timestamp: 200 })
+	));
+}
+
+#[tokio::test]
+async fn test_stream_orders_from_created_before() {
+	let order = |id: &str| order_json(id, "PENDING", json!({ "creation_timestamp": 200 }));
+	let path = "/api/1/listorders?state=PENDING&pair=ETHXBT&created_before=201&limit=2";
+	let first = mock("GET", path)
+		.with_body(format!(r#"{{"orders":[{},{}]}}"#, order("H"), order("I")))
+		.expect(1)
+		.create();
+	// H completes in the meantime, so it is no longer listed as pending.
+	let second = mock("GET", path)
+		.with_body(format!(r#"{{"orders":[{}]}}"#, order("I")))
+		.expect(1)
+		.create();
+
+	let client = mock_client();
+	let ids: Vec<String> = client
+		.orders()
+		.filter_state(OrderState::PENDING)
+		.filter_pair(TradingPair::ETHXBT)
+		.filter_created_before(201)
+		.filter_limit(2)
+		.stream()
+		.map(|order| order.unwrap().order_id)
+		.collect()
+		.await;
+	assert_eq!(ids, vec!["H", "I"]);
+	first.assert();
+	second.assert();
+}
+
+#[tokio::test]
+async fn test_limit_order_validates_stop_price() {
+	let market: luno::market::MarketInfo = serde_json::from_str(r#"{"market_id":"XBTZAR","trading_status":"ACTIVE","base_currency":"XBT","counter_currency":"ZAR","min_volume":"0.0005","max_volume":"100.0","volume_scale":4,"min_price":"100","max_price":"10000000","price_scale":0,"fee_scale":8}"#).unwrap();
+	let post = mock("POST", "/api/1/postorder")
+		.match_body(Matcher::UrlEncoded("stop_price".into(), "450001".into()))
+		.with_body(r#"{"order_id":"BXMC2CJ7HNB88U4"}"#)
+		.expect(1)
+		.create();
+
+	let client = mock_client();
+	client
+		.limit_order(
+			TradingPair::XBTZAR,
+			LimitOrderType::BID,
+			dec!(0.01),
+			dec!(460_000),
+		)
+		.with_stop_price(dec!(450_000.6))
+		.with_market_info(&market)
+		.post()
+		.await
+		.unwrap();
+	assert!(matches!(
+		client
+			.limit_order(
+				TradingPair::XBTZAR,
+				LimitOrderType::BID,
+				dec!(0.01),
+				dec!(460_000),
+			)
+			.with_stop_price(dec!(99))
+			.with_market_info(&market)
+			.post()
+			.await,
+		Err(LunoError::InvalidRequest(_))
+	));
+	post.assert();
+}
@@ -0,0 +1,88 @@
+use std::time::{Duration, Instant};
+
+use mockito::mock;
+use rust_decimal_macros::dec;
+
+use luno::{error::LunoError, LimitOrderType, LunoClient, RetryPolicy, TradingPair};
+
+#[tokio::test]
+async fn test_rate_limited() {
+	let _m = mock("GET", "/api/1/ticker?pair=XBTZAR")
+		.with_status(429)
+		.with_header("retry-after", "1")
+		.with_body(r#"{"error":"Too many requests","error_code":"ErrTooManyRequests"}"#)
+		.create();
+
+	let client = LunoClient::builder("TEST_KEY", "TEST_SECRET")
+		.with_base_url(&format!("{}/api/1/", mockito::server_url()))
+		.build()
+		.unwrap();
+
+	match client.get_ticker(TradingPair::XBTZAR).await {
+		Err(LunoError::RateLimited { retry_after }) => {
+			assert_eq!(retry_after, Some(Duration::from_secs(1)))
+		}
+		result => panic!("unexpected result: {:?}", result),
+	}
+}
+
+#[tokio::test]
+async fn test_retries_rate_limited_order_placement() {
+	let m = mock("POST", "/api/1/postorder")
+		.with_status(429)
+		.with_header("retry-after", "0")
+		.expect(2)
+		.create();
+
+	let client = LunoClient::builder("TEST_KEY", "TEST_SECRET")
+		.with_base_url(&format!("{}/api/1/", mockito::server_url()))
+		.with_retry_policy(RetryPolicy {
+			max_attempts: 2,
+			initial_backoff: Duration::from_millis(1),
+			..RetryPolicy::default()
+		})
+		.build()
+		.unwrap();
+
+	let result = client
+		.limit_order(
+			TradingPair::XBTZAR,
+			LimitOrderType::BID,
+			dec!(0.01),
+			dec!(100_000),
+		)
+		.post()
+		.await;
+	assert!(matches!(result, Err(LunoError::RateLimited { .. })));
+	m.assert();
+}
+
+#[tokio::test]
+async fn test_retry_waits_for_retry_after() {
+	let limited = mock("GET", "/api/1/ticker?pair=ETHZAR")
+		.with_status(429)
+		.with_header("retry-after", "1")
+		.expect(1)
+		.create();
+	let ticker = mock("GET", "/api/1/ticker?pair=ETHZAR")
+		.with_body(r#"{"timestamp":1405413955793,"bid":"6801.00","ask":"6900.00","last_trade":"6900.00","rolling_24_hour_volume":"12.455579","pair":"ETHZAR","status":"ACTIVE"}"#)
+		.expect(1)
+		.create();
+
+	let client = LunoClient::builder("TEST_KEY", "TEST_SECRET")
+		.with_base_url(&format!("{}/api/1/", mockito::server_url()))
+		.without_rate_limit()
+		.with_retry_policy(RetryPolicy {
+			max_attempts: 2,
+			initial_backoff: Duration::from_millis(1),
+			..RetryPolicy::default()
+		})
+		.build()
+		.unwrap();
+
+	let started = Instant::now();
+	client.get_ticker(TradingPair::ETHZAR).await.unwrap();
+	assert!(started.elapsed() >= Duration::from_secs(1));
+	limited.assert();
+	ticker.assert();
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use mockito::mock;
+use rust_decimal_macros::dec;
+
+use luno::{error::LunoError, LimitOrderType, LunoClient, RetryPolicy, TradingPair};
+
+fn retrying_client() -> LunoClient {
+	LunoClient::builder("TEST_KEY", "TEST_SECRET")
+		.with_base_url(&format!("{}/api/1/", mockito::server_url()))
+		.with_retry_policy(RetryPolicy {
+			max_attempts: 3,
+			initial_backoff: Duration::from_millis(1),
+			..RetryPolicy::default()
+		})
+		.build()
+		.unwrap()
+}
+
+#[tokio::test]
+async fn test_retries_idempotent_requests() {
+	let m = mock("GET", "/api/1/ticker?pair=XBTZAR")
+		.with_status(503)
+		.expect(3)
+		.create();
+
+	let result = retrying_client().get_ticker(TradingPair::XBTZAR).await;
+	assert!(matches!(result, Err(LunoError::UnexpectedResponse { .. })));
+	m.assert();
+}
+
+#[tokio::test]
+async fn test_does_not_retry_client_errors() {
+	let m = mock("GET", "/api/1/orders/BXMC2CJ7HNB88U4")
+		.with_status(404)
+		.with_body(r#"{"error":"Order not found","error_code":"ErrOrderNotFound"}"#)
+		.expect(1)
+		.create();
+
+	assert!(retrying_client()
+		.get_order("BXMC2CJ7HNB88U4")
+		.await
+		.is_err());
+	m.assert();
+}
+
+#[tokio::test]
+async fn test_does_not_retry_order_placement() {
+	let m = mock("POST", "/api/1/postorder")
+		.with_status(503)
+		.expect(1)
+		.create();
+
+	assert!(retrying_client()
+		.limit_order(
+			TradingPair::XBTZAR,
+			LimitOrderType::BID,
+			dec!(0.01),
+			dec!(100_000)
+		)
+		.post()
+		.await
+		.is_err());
+	m.assert();
+}
@@ -0,0 +1,196 @@
+mod common;
+
+use mockito::{mock, Matcher};
+use rust_decimal_macros::dec;
+
+use luno::{
+	error::{ErrorCode, LunoError},
+	Currency,
+};
+
+use common::mock_client;
+
+#[tokio::test]
+async fn test_send() {
+	let m = mock("POST", "/api/1/send")
+		.match_body(Matcher::AllOf(vec![
+			Matcher::UrlEncoded("amount".into(), "25".into()),
+			Matcher::UrlEncoded("currency".into(), "XRP".into()),
+			Matcher::UrlEncoded(
+				"address".into(),
+				"rHcFoo6a9qT5NHiVn1THQRhsEGcxtYCV4d".into(),
+			),
+			Matcher::UrlEncoded("has_destination_tag".into(), "true".into()),
+			Matcher::UrlEncoded("destination_tag".into(), "12345".into()),
+			Matcher::UrlEncoded("external_id".into(), "payout-1".into()),
+		]))
+		.with_body(r#"{"success":true,"withdrawal_id":"1234567"}"#)
+		.expect(1)
+		.create();
+
+	let response = mock_client()
+		.send(
+			dec!(25),
+			Currency::XRP,
+			"rHcFoo6a9qT5NHiVn1THQRhsEGcxtYCV4d",
+		)
+		.with_destination_tag(12345)
+		.with_external_id("payout-1")
+		.post()
+		.await
+		.unwrap();
+	assert!(response.success);
+	assert_eq!(response.withdrawal_id, "1234567");
+	m.assert();
+}
+
+#[tokio::test]
+async fn test_send_invalid_address() {
+	let _m = mock("POST", "/api/1/send")
+		.match_body(Matcher::UrlEncoded(
+			"address".into(),
+			"not-an-address".into(),
+		))
+		.with_status(400)
+		.with_body(r#"{"error":"Invalid address","error_code":"ErrInvalidAddress"}"#)
+		.create();
+
+	let result = mock_client()
+		.send(dec!(0.1), Currency::XBT, "not-an-address")
+		.post()
+		.await;
+	assert!(matches!(
+		result,
+		Err(LunoError::ApiError {
+			code: ErrorCode::ErrInvalidAddress,
+			..
+		})
+	));
+}
+
+#[tokio::test]
+async fn test_send_fee() {
+	let _m = mock(
+		"GET",
+		"/api/1/send_fee?amount=0.5&currency=ETH&address=0xb794f5ea0ba39494ce839613fffba74279579268",
+	)
+	.with_body(r#"{"currency":"ETH","fee":"0.0021"}"#)
+	.create();
+
+	let fee = mock_client()
+		.send_fee(
+			dec!(0.5),
+			Currency::ETH,
+			"0xb794f5ea0ba39494ce839613fffba74279579268",
+		)
+		.await
+		.unwrap();
+	assert_eq!(fee.currency, Currency::ETH);
+	assert_eq!(fee.fee, dec!(0.0021));
+}
+
+#[tokio::test]
+async fn test_validate_address() {
+	let m = mock("POST", "/api/1/address/validate")
+		.match_body(Matcher::AllOf(vec![
+			Matcher::UrlEncoded("address".into(), "bc1qvalidateexample".into()),
+			Matcher::UrlEncoded("currency".into(), "XBT".into()),
+			Matcher::UrlEncoded("is_private_wallet".into(), "true".into()),
+			Matcher::UrlEncoded("private_wallet_name".into(), "Ledger".into()),
+			Matcher::UrlEncoded("beneficiary_name".into(), "Satoshi Nakamoto".into()),
+			Matcher::UrlEncoded("is_legal_entity".into(), "false".into()),
+		]))
+		.with_body(r#"{"success":true}"#)
+		.expect(1)
+		.create();
+
+	let validation = mock_client()
+		.validate_address("bc1qvalidateexample", Currency::XBT)
+		.private_wallet("Ledger")
+		.with_beneficiary("Satoshi Nakamoto", false)
+		.post()
+		.await
+		.unwrap();
+	assert!(validation.success);
+	m.assert();
+}
+
+#[tokio::test]
+async fn test_send_with_address_validation() {
+	let validate = mock("POST", "/api/1/address/validate")
+		.match_body(Matcher::UrlEncoded(
+			"address".into(),
+			"bc1qrejectedexample".into(),
+		))
+		.with_status(400)
+		.with_body(r#"{"error":"Invalid address","error_code":"ErrInvalidAddress"}"#)
+		.expect(1)
+		.create();
+	let send = mock("POST", "/api/1/send")
+		.match_body(Matcher::UrlEncoded(
+			"address".into(),
+			"bc1qrejectedexample".into(),
+		))
+		.expect(0)
+		.create();
+
+	let client = mock_client();
+	let result = client
+		.send(dec!(0.1), Currency::XBT, "bc1qrejectedexample")
+		.with_address_validation(client.validate_address("bc1qrejectedexample", Currency::XBT))
+		.post()
+		.await;
+	assert_eq!(
+		result.unwrap_err().error_code(),
+		Some(&ErrorCode::ErrInvalidAddress)
+	);
+	validate.assert();
+	send.assert();
+}
+
+#[tokio::test]
+async fn test_send_with_travel_rule_validation() {
+	let validate = mock("POST", "/api/1/address/validate")
+		.match_body(Matcher::AllOf(vec![
+			Matcher::UrlEncoded("address".into(), "rTravelRuleExample".into()),
+			Matcher::UrlEncoded("destination_tag".into(), "42".into()),
+			Matcher::UrlEncoded("is_private_wallet".into(), "true".into()),
+			Matcher::UrlEncoded("private_wallet_name".into(), "Ledger".into()),
+		]))
+		.with_body(r#"{"success":true}"#)
+		.expect(1)
+		.create();
+	let send = mock("POST", "/api/1/send")
+		.match_body(Matcher::UrlEncoded(
+			"address".into(),
+			"rTravelRuleExample".into(),
+		))
+		.with_body(r#"{"success":true,"withdrawal_id":"3012"}"#)
+		.expect(1)
+		.create();
+
+	let client = mock_client();
+	let mut validation = client.validate_address("rTravelRuleExample", Currency::XRP);
+	validation.private_wallet("Ledger");
+	let response = client
+		.send(dec!(10), Currency::XRP, "rTravelRuleExample")
+		.with_destination_tag(42)
+		.with_address_validation(validation)
+		.post()
+		.await
+		.unwrap();
+	assert_eq!(response.withdrawal_id, "3012");
+	validate.assert();
+	send.assert();
+}
+
+#[tokio::test]
+async fn test_send_rejects_validation_for_another_address() {
+	let client = mock_client();
+	let result = client
+		.send(dec!(0.1), Currency::XBT, "bc1qsendexample")
+		.with_address_validation(client.validate_address("bc1qotherexample", Currency::XBT))
+		.post()
+		.await;
+	assert!(matches!(result, Err(LunoError::InvalidRequest(_))));
+}
@@ -0,0 +1,217 @@
+mod common;
+
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use mockito::mock;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use tokio::net::TcpListener;
+use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+use luno::{error::LunoError, orders::OrderState, LunoClient, TradingPair, UserEvent};
+
+use common::order_json;
+
+const SNAPSHOT: &str = r#"{
+	"sequence": "1",
+	"asks": [{"id": "ask-1", "price": "101.00", "volume": "1.0"}],
+	"bids": [{"id": "bid-1", "price": "99.00", "volume": "2.0"}],
+	"status": "ACTIVE",
+	"timestamp": 1000
+}"#;
+
+/// Accepts a websocket connection on `listener`, checks the credentials sent by the
+/// client and then sends `messages`.
+async fn serve(listener: &TcpListener, messages: &[&str]) {
+	let (tcp, _) = listener.accept().await.unwrap();
+	let mut socket = accept_async(tcp).await.unwrap();
+	match socket.next().await.unwrap().unwrap() {
+		Message::Text(auth) => assert!(auth.contains("TEST_KEY")),
+		message => panic!("unexpected message: {:?}", message),
+	}
+	for message in messages {
+		socket
+			.send(Message::Text(message.to_string()))
+			.await
+			.unwrap();
+	}
+}
+
+async fn stream_client(listener: &TcpListener) -> LunoClient {
+	LunoClient::builder("TEST_KEY", "TEST_SECRET")
+		.with_stream_url(&format!("ws://{}/api/1/", listener.local_addr().unwrap()))
+		.build()
+		.unwrap()
+}
+
+#[tokio::test]
+async fn test_applies_updates() {
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let client = stream_client(&listener).await;
+	let server = tokio::spawn(async move {
+		serve(
+			&listener,
+			&[
+				SNAPSHOT,
+				"",
+				r#"{"sequence": "2", "create_update": {"order_id": "bid-2", "type": "BID", "price": "100.00", "volume": "0.5"}, "timestamp": 1001}"#,
+				r#"{"sequence": "3", "trade_updates": [{"base": "0.4", "counter": "40.4", "maker_order_id": "ask-1", "taker_order_id": "bid-3"}], "timestamp": 1002}"#,
+				r#"{"sequence": "4", "delete_update": {"order_id": "bid-1"}, "timestamp": 1003}"#,
+			],
+		)
+		.await;
+		listener
+	});
+
+	let mut stream = client.stream_orderbook(TradingPair::XBTZAR).await.unwrap();
+	assert_eq!(stream.sequence(), 1);
+	assert_eq!(stream.status(), "ACTIVE");
+	for sequence in 2..=4 {
+		assert_eq!(stream.next_update().await.unwrap().sequence, sequence);
+	}
+
+	let orderbook = stream.orderbook();
+	assert_eq!(orderbook.timestamp, 1003);
+	assert_eq!(orderbook.asks.len(), 1);
+	assert_eq!(orderbook.asks[0].volume, dec!(0.6));
+	assert_eq!(orderbook.bids.len(), 1);
+	assert_eq!(orderbook.bids[0].price, dec!(100.00));
+	server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_resubscribes_on_gap() {
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let client = stream_client(&listener).await;
+	let server = tokio::spawn(async move {
+		serve(
+			&listener,
+			&[
+				SNAPSHOT,
+				r#"{"sequence": "3", "delete_update": {"order_id": "ask-1"}, "timestamp": 1002}"#,
+			],
+		)
+		.await;
+		serve(
+			&listener,
+			&[
+				r#"{"sequence": "10", "asks": [], "bids": [], "status": "ACTIVE", "timestamp": 2000}"#,
+				r#"{"sequence": "11", "create_update": {"order_id": "ask-2", "type": "ASK", "price": "102.00", "volume": "1.0"}, "timestamp": 2001}"#,
+			],
+		)
+		.await;
+	});
+
+	let mut stream = client.stream_orderbook(TradingPair::XBTZAR).await.unwrap();
+	assert_eq!(stream.next_update().await.unwrap().sequence, 11);
+
+	let orderbook = stream.orderbook();
+	assert_eq!(orderbook.asks.len(), 1);
+	assert_eq!(orderbook.asks[0].price, dec!(102.00));
+	assert!(orderbook.bids.is_empty());
+	server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_user_stream_reconnects() {
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let client = stream_client(&listener).await;
+	let server = tokio::spawn(async move {
+		serve(
+			&listener,
+			&[
+				r#"{"type": "order_status", "timestamp": 1000, "order_status_update": {"order_id": "BXMC2CJ7HNB88U4", "market_id": "XBTZAR", "status": "PENDING"}}"#,
+				r#"{"type": "something_new", "timestamp": 1001}"#,
+			],
+		)
+		.await;
+		serve(
+			&listener,
+			&[
+				r#"{"type": "order_fill", "timestamp": 1002, "order_fill_update": {"order_id": "BXMC2CJ7HNB88U4", "market_id": "XBTZAR", "base_fill": "0.1", "counter_fill": "10.0", "base_delta": "0.1", "counter_delta": "10.0", "base_fee": "0.0", "counter_fee": "0.01", "base_fee_delta": "0.0", "counter_fee_delta": "0.01"}}"#,
+				r#"{"type": "balance_update", "timestamp": 1003, "balance_update": {"account_id": "12345", "row_index": 7, "balance": "90.0", "balance_delta": "-10.0", "available": "90.0", "available_delta": "-10.0"}}"#,
+			],
+		)
+		.await;
+	});
+
+	let mut stream = client.user_stream().await.unwrap();
+	match stream.next().await.unwrap().unwrap() {
+		UserEvent::OrderStatus(update) => {
+			assert_eq!(update.timestamp, 1000);
+			assert_eq!(update.status, OrderState::PENDING);
+		}
+		event => panic!("unexpected event: {:?}", event),
+	}
+	// The first connection is dropped without a closing handshake.
+	assert!(matches!(
+		stream.next().await.unwrap(),
+		Err(LunoError::WebSocketError(_))
+	));
+	match stream.next().await.unwrap().unwrap() {
+		UserEvent::OrderFill(update) => assert_eq!(update.base_fill, dec!(0.1)),
+		event => panic!("unexpected event: {:?}", event),
+	}
+	match stream.next().await.unwrap().unwrap() {
+		UserEvent::BalanceUpdate(update) => assert_eq!(update.row_index, 7),
+		event => panic!("unexpected event: {:?}", event),
+	}
+	server.await.unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_user_stream_ends_after_failed_reconnects() {
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let client = stream_client(&listener).await;
+	let server = tokio::spawn(async move {
+		serve(
+			&listener,
+			&[
+				r#"{"type": "order_status", "timestamp": 1000, "order_status_update": {"order_id": "BXMC2CJ7HNB88U4", "market_id": "XBTZAR", "status": "COMPLETE"}}"#,
+			],
+		)
+		.await;
+	});
+
+	let mut stream = client.user_stream().await.unwrap();
+	assert!(matches!(
+		stream.next().await.unwrap(),
+		Ok(UserEvent::OrderStatus(_))
+	));
+	server.await.unwrap();
+
+	// The server is gone, so every attempt to reconnect fails.
+	let errors: Vec<_> = stream.collect().await;
+	assert_eq!(errors.len(), 6);
+	assert!(errors.iter().all(|error| error.is_err()));
+}
+
+#[tokio::test]
+async fn test_order_handle_polls_through_user_stream_errors() {
+	let _pending = mock("GET", "/api/1/orders/TRACK-WS")
+		.with_body(order_json("TRACK-WS", "PENDING", json!({})))
+		.expect(1)
+		.create();
+	let _complete = mock("GET", "/api/1/orders/TRACK-WS")
+		.with_body(order_json("TRACK-WS", "COMPLETE", json!({})))
+		.create();
+
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let client = LunoClient::builder("TEST_KEY", "TEST_SECRET")
+		.with_base_url(&format!("{}/api/1/", mockito::server_url()))
+		.with_stream_url(&format!("ws://{}/api/1/", listener.local_addr().unwrap()))
+		.build()
+		.unwrap();
+	// The connection is dropped straight away, so the stream returns an error.
+	let server = tokio::spawn(async move { serve(&listener, &[]).await });
+
+	let stream = client.user_stream().await.unwrap();
+	let mut handle = client.track_order("TRACK-WS");
+	handle
+		.with_poll_interval(Duration::from_millis(5))
+		.with_user_stream(stream);
+	let order = handle.wait(Duration::from_secs(5)).await.unwrap();
+	assert_eq!(order.state, OrderState::COMPLETE);
+	server.await.unwrap();
+}
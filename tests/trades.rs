@@ -0,0 +1,101 @@
+mod common;
+
+use futures::StreamExt;
+use mockito::mock;
+
+use luno::TradingPair;
+
+use common::mock_client;
+
+fn trade(sequence: u64) -> String {
+	format!(
+		r#"{{"base":"0.1","counter":"100.0","fee_base":"0.0","is_buy":true,"order_id":"BXMC2CJ7HNB88U4","pair":"XBTZAR","price":"1000.0","timestamp":1367849297609,"type":"BID","volume":"0.1","sequence":{}}}"#,
+		sequence
+	)
+}
+
+#[tokio::test]
+async fn test_stream_own_trades() {
+	let first = mock("GET", "/api/1/listtrades?pair=XBTZAR&limit=2")
+		.with_body(format!(r#"{{"trades":[{},{}]}}"#, trade(1), trade(2)))
+		.expect(1)
+		.create();
+	let last = mock("GET", "/api/1/listtrades?pair=XBTZAR&limit=2&after_seq=3")
+		.with_body(format!(r#"{{"trades":[{}]}}"#, trade(3)))
+		.expect(1)
+		.create();
+
+	let client = mock_client();
+	let sequences: Vec<u64> = client
+		.list_own_trades(TradingPair::XBTZAR)
+		.limit(2)
+		.stream()
+		.map(|trade| trade.unwrap().sequence)
+		.collect()
+		.await;
+	assert_eq!(sequences, vec![1, 2, 3]);
+	first.assert();
+	last.assert();
+}
+
+#[tokio::test]
+async fn test_stream_own_trades_descending() {
+	let first = mock(
+		"GET",
+		"/api/1/listtrades?pair=ETHZAR&limit=2&sort_desc=true",
+	)
+	.with_body(format!(r#"{{"trades":[{},{}]}}"#, trade(9), trade(8)))
+	.expect(1)
+	.create();
+	let last = mock(
+		"GET",
+		"/api/1/listtrades?pair=ETHZAR&limit=2&before_seq=8&sort_desc=true",
+	)
+	.with_body(r#"{"trades":[]}"#)
+	.expect(1)
+	.create();
+
+	let client = mock_client();
+	let sequences: Vec<u64> = client
+		.list_own_trades(TradingPair::ETHZAR)
+		.limit(2)
+		.sort_desc(true)
+		.stream()
+		.map(|trade| trade.unwrap().sequence)
+		.collect()
+		.await;
+	assert_eq!(sequences, vec![9, 8]);
+	first.assert();
+	last.assert();
+}
+
+#[tokio::test]
+async fn test_stream_own_trades_before() {
+	let first = mock(
+		"GET",
+		"/api/1/listtrades?pair=XBTEUR&limit=2&before=1367849297700",
+	)
+	.with_body(format!(r#"{{"trades":[{},{}]}}"#, trade(6), trade(5)))
+	.expect(1)
+	.create();
+	let last = mock(
+		"GET",
+		"/api/1/listtrades?pair=XBTEUR&limit=2&before=1367849297700&before_seq=5",
+	)
+	.with_body(format!(r#"{{"trades":[{}]}}"#, trade(4)))
+	.expect(1)
+	.create();
+
+	let client = mock_client();
+	let sequences: Vec<u64> = client
+		.list_own_trades(TradingPair::XBTEUR)
+		.limit(2)
+		.before(1_367_849_297_700)
+		.stream()
+		.map(|trade| trade.unwrap().sequence)
+		.collect()
+		.await;
+	assert_eq!(sequences, vec![6, 5, 4]);
+	first.assert();
+	last.assert();
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use futures::StreamExt;
+use mockito::mock;
+
+use luno::TransactionsBuilder;
+
+use common::mock_client;
+
+/// Builds a page of rows in the descending order Luno returns them in.
+fn page(account_id: &str, rows: std::ops::Range<u64>) -> String {
+	let transactions: Vec<String> = rows
+		.rev()
+		.map(|row| {
+			format!(
+				r#"{{"row_index":{},"timestamp":1367849297609,"balance":"1.0","available":"1.0","balance_delta":"1.0","available_delta":"1.0","currency":"XBT","description":"Deposit"}}"#,
+				row
+			)
+		})
+		.collect();
+	format!(
+		r#"{{"id":"{}","transactions":[{}]}}"#,
+		account_id,
+		transactions.join(",")
+	)
+}
+
+async fn rows(builder: &TransactionsBuilder<'_>) -> Vec<u64> {
+	builder
+		.stream()
+		.map(|transaction| transaction.unwrap().row_index)
+		.collect()
+		.await
+}
+
+#[tokio::test]
+async fn test_stream_oldest_first_resumes_after_row() {
+	let first = mock(
+		"GET",
+		"/api/1/accounts/1001/transactions?min_row=1001&max_row=2001",
+	)
+	.with_body(page("1001", 1001..2001))
+	.expect(1)
+	.create();
+	let last = mock(
+		"GET",
+		"/api/1/accounts/1001/transactions?min_row=2001&max_row=3001",
+	)
+	.with_body(page("1001", 2001..2003))
+	.expect(1)
+	.create();
+
+	let client = mock_client();
+	let rows = rows(client.transactions("1001").after_row(1000)).await;
+	assert_eq!(rows, (1001..2003).collect::<Vec<_>>());
+	first.assert();
+	last.assert();
+}
+
+#[tokio::test]
+async fn test_stream_newest_first_stops_at_first_row() {
+	let first = mock(
+		"GET",
+		"/api/1/accounts/1002/transactions?min_row=3&max_row=1003",
+	)
+	.with_body(page("1002", 3..1003))
+	.expect(1)
+	.create();
+	let last = mock(
+		"GET",
+		"/api/1/accounts/1002/transactions?min_row=1&max_row=3",
+	)
+	.with_body(page("1002", 1..3))
+	.expect(1)
+	.create();
+
+	let client = mock_client();
+	let rows = rows(client.transactions("1002").newest_first().before_row(1003)).await;
+	assert_eq!(rows, (1..1003).rev().collect::<Vec<_>>());
+	first.assert();
+	last.assert();
+}
+
+#[tokio::test]
+async fn test_stream_newest_first_wraps_around_latest_row() {
+	let m = mock(
+		"GET",
+		"/api/1/accounts/1003/transactions?min_row=-1000&max_row=0",
+	)
+	.with_body(page("1003", 1..4))
+	.expect(1)
+	.create();
+
+	let client = mock_client();
+	let rows = rows(client.transactions("1003").newest_first()).await;
+	assert_eq!(rows, vec![3, 2, 1]);
+	m.assert();
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use mockito::{mock, Matcher};
+use rust_decimal_macros::dec;
+
+use luno::{Currency, WithdrawalStatus, WithdrawalType};
+
+use common::mock_client;
+
+fn withdrawal(id: &str, status: &str) -> String {
+	format!(
+		r#"{{"id":"{}","status":"{}","created_at":1557140000000,"type":"ZAR_EFT","currency":"ZAR","amount":"1000.00","fee":"8.50"}}"#,
+		id, status
+	)
+}
+
+#[tokio::test]
+async fn test_list_withdrawals() {
+	let _m = mock("GET", "/api/1/withdrawals?limit=2&before_id=1003")
+		.with_body(format!(
+			r#"{{"withdrawals":[{},{}]}}"#,
+			withdrawal("1002", "COMPLETED"),
+			withdrawal("1001", "REFUNDED")
+		))
+		.create();
+
+	let withdrawals = mock_client()
+		.list_withdrawals()
+		.limit(2)
+		.before_id("1003")
+		.list()
+		.await
+		.unwrap();
+	assert_eq!(withdrawals.len(), 2);
+	assert_eq!(withdrawals[0].status, WithdrawalStatus::COMPLETED);
+	assert_eq!(withdrawals[0].currency, Currency::ZAR);
+	assert_eq!(
+		withdrawals[1].status,
+		WithdrawalStatus::Other("REFUNDED".to_owned())
+	);
+}
+
+#[tokio::test]
+async fn test_create_and_cancel_withdrawal() {
+	let create = mock("POST", "/api/1/withdrawals")
+		.match_body(Matcher::AllOf(vec![
+			Matcher::UrlEncoded("type".into(), "ZAR_EFT".into()),
+			Matcher::UrlEncoded("amount".into(), "1000.00".into()),
+			Matcher::UrlEncoded("beneficiary_id".into(), "2001".into()),
+			Matcher::UrlEncoded("fast".into(), "true".into()),
+			Matcher::UrlEncoded("reference".into(), "Sweep".into()),
+		]))
+		.with_body(withdrawal("1004", "PENDING"))
+		.expect(1)
+		.create();
+	let cancel = mock("DELETE", "/api/1/withdrawals/1004")
+		.with_body(withdrawal("1004", "CANCELLED"))
+		.expect(1)
+		.create();
+
+	let client = mock_client();
+	let created = client
+		.create_withdrawal(WithdrawalType::ZAR_EFT, dec!(1000.00))
+		.with_beneficiary("2001")
+		.fast()
+		.with_reference("Sweep")
+		.post()
+		.await
+		.unwrap();
+	assert_eq!(created.status, WithdrawalStatus::PENDING);
+	assert_eq!(created.withdrawal_type, Some(WithdrawalType::ZAR_EFT));
+
+	let cancelled = client.cancel_withdrawal(&created.id).await.unwrap();
+	assert_eq!(cancelled.status, WithdrawalStatus::CANCELLED);
+	create.assert();
+	cancel.assert();
+}
+
+#[tokio::test]
+async fn test_get_withdrawal() {
+	let _m = mock("GET", "/api/1/withdrawals/1005")
+		.with_body(withdrawal("1005", "PROCESSING"))
+		.create();
+
+	let withdrawal = mock_client().get_withdrawal("1005").await.unwrap();
+	assert_eq!(withdrawal.status, WithdrawalStatus::PROCESSING);
+	assert_eq!(withdrawal.fee, dec!(8.50));
+}